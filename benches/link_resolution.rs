@@ -0,0 +1,160 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use serde_json::{json, Value};
+
+// The resolver of the crate, which is not public, with the modules it uses
+#[allow(dead_code)]
+#[path = "../src/link_resolver.rs"]
+mod link_resolver;
+mod error {
+    pub use contentful::{Error, Result};
+}
+mod models {
+    pub use contentful::models::LinkReference;
+}
+
+const ENTRIES: usize = 1000;
+
+/// A page of a thousand entries that each link to an included asset and to one of
+/// `linked_entries` included entries, which link on to a category and an asset.
+fn entries_response(linked_entries: usize) -> Value {
+    let link = |link_type: &str, id: String| json!({ "sys": { "type": "Link", "linkType": link_type, "id": id } });
+    let items = (0..ENTRIES)
+        .map(|i| {
+            json!({
+                "sys": { "id": format!("item-{}", i), "type": "Entry" },
+                "fields": {
+                    "title": format!("Item {}", i),
+                    "related": link("Entry", format!("entry-{}", i % linked_entries)),
+                    "image": link("Asset", format!("asset-{}", i))
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    let entries = (0..ENTRIES)
+        .map(|i| {
+            json!({
+                "sys": { "id": format!("entry-{}", i), "type": "Entry" },
+                "fields": {
+                    "title": format!("Entry {}", i),
+                    "category": link("Entry", format!("category-{}", i % 10)),
+                    "image": link("Asset", format!("asset-{}", i)),
+                    "tags": ["a", "b", "c"]
+                }
+            })
+        })
+        .chain((0..10).map(|i| {
+            json!({
+                "sys": { "id": format!("category-{}", i), "type": "Entry" },
+                "fields": { "title": format!("Category {}", i) }
+            })
+        }))
+        .collect::<Vec<_>>();
+    let assets = (0..ENTRIES)
+        .map(|i| {
+            json!({
+                "sys": { "id": format!("asset-{}", i), "type": "Asset" },
+                "fields": {
+                    "title": format!("Asset {}", i),
+                    "file": {
+                        "fileName": "image.png",
+                        "contentType": "image/png",
+                        "url": format!("//images.ctfassets.net/{}.png", i),
+                        "details": { "size": 1024, "image": { "width": 10, "height": 20 } }
+                    }
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    json!({
+        "sys": { "type": "Array" },
+        "total": ENTRIES,
+        "skip": 0,
+        "limit": ENTRIES,
+        "items": items,
+        "includes": { "Entry": entries, "Asset": assets }
+    })
+}
+
+/// Link resolution of 0.8.0, which searches the includes for each link and clones the response
+/// and every linked entry and asset, to compare the indexed resolver with.
+mod linear {
+    use serde_json::Value;
+
+    pub fn resolve_response(json: &Value) -> Value {
+        let mut items = json["items"].clone();
+        resolve_array(&mut items, &json["includes"]);
+        items
+    }
+
+    fn resolve_array(value: &mut Value, includes: &Value) {
+        for item in value.as_array_mut().unwrap() {
+            if item.is_object() {
+                resolve_object(item, includes);
+            }
+        }
+    }
+
+    fn resolve_object(value: &mut Value, includes: &Value) {
+        if value["sys"]["type"] == "Entry" {
+            resolve_entry(value, includes);
+        } else if value["sys"]["type"] == "Link" {
+            resolve_link(value, includes);
+        }
+    }
+
+    fn resolve_entry(value: &mut Value, includes: &Value) {
+        let fields = value.get_mut("fields").unwrap();
+        for (_field_name, field_value) in fields.as_object_mut().unwrap() {
+            if field_value.is_object() {
+                resolve_object(field_value, includes);
+            } else if field_value.is_array() {
+                resolve_array(field_value, includes);
+            }
+        }
+        *value = fields.clone();
+    }
+
+    fn resolve_link(value: &mut Value, includes: &Value) {
+        let link_type = value["sys"]["linkType"].as_str().unwrap().to_string();
+        let link_id = value["sys"]["id"].clone();
+        let included = includes[&link_type]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|included| included["sys"]["id"] == link_id);
+        if let Some(included) = included {
+            let mut included = included.clone();
+            if link_type == "Entry" {
+                resolve_entry(&mut included, includes);
+            } else {
+                included = included["fields"].clone();
+            }
+            *value = included;
+        }
+    }
+}
+
+/// Resolves the links of a parsed response like `get_entries` with `include=2`, with the
+/// indexed resolver against the linear search of 0.8.0. Items link to distinct entries, or share
+/// 50 entries, e.g. authors.
+fn link_resolution(c: &mut Criterion) {
+    for (group_name, linked_entries) in [("distinct links", ENTRIES), ("shared links", 50)] {
+        let response = entries_response(linked_entries);
+        let mut group = c.benchmark_group(format!("resolve 1000 entries with {}", group_name));
+        group.bench_function("indexed", |b| {
+            b.iter_batched(
+                || response.clone(),
+                |mut response| {
+                    link_resolver::resolve_response(&mut response, false, 2).unwrap();
+                    response
+                },
+                BatchSize::LargeInput,
+            )
+        });
+        group.bench_function("linear", |b| b.iter(|| linear::resolve_response(&response)));
+        group.finish();
+    }
+}
+
+criterion_group!(benches, link_resolution);
+criterion_main!(benches);
@@ -1,24 +1,39 @@
+use crate::error::{Error, Result};
+use crate::http_client::HttpClient;
+use crate::link_resolver::{fields_with_sys, resolve_response, DEFAULT_INCLUDE_DEPTH};
+use crate::models::{
+    Asset, Collection, ContentType, Entry, LinkReference, Locale, Space, SyncItem, SyncResult,
+    SystemProperties, Tag,
+};
 use crate::query_builder::QueryBuilder;
-use crate::{http_client, models::Entry};
+use crate::retry_policy::RetryPolicy;
+use futures::stream::{self, Stream, TryStreamExt};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use std::time::Duration;
 
+#[derive(Clone)]
 pub struct ContentfulClient {
     delivery_api_access_token: String,
     space_id: String,
     base_url: String,
     environment_id: String,
+    remove_unresolved: bool,
+    http_client: HttpClient,
 }
 
 impl ContentfulClient {
+    /// The Content Delivery API host.
+    pub const BASE_URL: &str = "https://cdn.contentful.com";
+    /// The Content Delivery API host for spaces with EU data residency.
+    pub const EU_BASE_URL: &str = "https://cdn.eu.contentful.com";
+    /// The Content Preview API host.
+    pub const PREVIEW_BASE_URL: &str = "https://preview.contentful.com";
+    /// The Content Preview API host for spaces with EU data residency.
+    pub const EU_PREVIEW_BASE_URL: &str = "https://preview.eu.contentful.com";
+
     pub fn new(delivery_api_access_token: &str, space_id: &str) -> ContentfulClient {
-        let environment_id = "master".into();
-        ContentfulClient {
-            base_url: "https://cdn.contentful.com/spaces".into(),
-            delivery_api_access_token: delivery_api_access_token.into(),
-            space_id: space_id.into(),
-            environment_id,
-        }
+        ContentfulClient::builder(delivery_api_access_token, space_id).build()
     }
 
     pub fn with_environment<S>(
@@ -26,12 +41,49 @@ impl ContentfulClient {
         space_id: &str,
         environment_id: &str,
     ) -> ContentfulClient {
-        ContentfulClient {
-            base_url: "https://cdn.contentful.com/spaces".into(),
-            delivery_api_access_token: delivery_api_access_token.into(),
-            space_id: space_id.into(),
-            environment_id: environment_id.into(),
-        }
+        ContentfulClient::builder(delivery_api_access_token, space_id)
+            .environment(environment_id)
+            .build()
+    }
+
+    /// Creates a client for the Content Preview API, which also returns draft entries and assets.
+    pub fn preview(preview_api_access_token: &str, space_id: &str) -> ContentfulClient {
+        ContentfulClient::builder(preview_api_access_token, space_id)
+            .preview(true)
+            .build()
+    }
+
+    pub fn builder(delivery_api_access_token: &str, space_id: &str) -> ContentfulClientBuilder {
+        ContentfulClientBuilder::new(delivery_api_access_token, space_id)
+    }
+
+    fn get_space_url(&self) -> String {
+        let url = format!(
+            "{base_url}/{space_id}",
+            base_url = &self.base_url,
+            space_id = &self.space_id
+        );
+        url
+    }
+
+    fn get_locales_url(&self) -> String {
+        let url = format!(
+            "{base_url}/{space_id}/environments/{environment_id}/locales",
+            base_url = &self.base_url,
+            space_id = &self.space_id,
+            environment_id = &self.environment_id
+        );
+        url
+    }
+
+    fn get_tags_url(&self) -> String {
+        let url = format!(
+            "{base_url}/{space_id}/environments/{environment_id}/tags",
+            base_url = &self.base_url,
+            space_id = &self.space_id,
+            environment_id = &self.environment_id
+        );
+        url
     }
 
     fn get_entry_url(&self, entry_id: &str) -> String {
@@ -56,47 +108,141 @@ impl ContentfulClient {
         url
     }
 
-    pub async fn get_entry<T>(
-        &self,
-        entry_id: &str,
-    ) -> Result<Option<T>, Box<dyn std::error::Error>>
+    fn get_asset_url(&self, asset_id: &str) -> String {
+        let url = format!(
+            "{base_url}/{space_id}/environments/{environment_id}/assets/{asset_id}",
+            base_url = &self.base_url,
+            space_id = &self.space_id,
+            environment_id = &self.environment_id,
+            asset_id = &asset_id
+        );
+        url
+    }
+
+    fn get_assets_query_string_url(&self, query_string: &str) -> String {
+        let url = format!(
+            "{base_url}/{space_id}/environments/{environment}/assets{query_string}",
+            base_url = &self.base_url,
+            space_id = &self.space_id,
+            environment = &self.environment_id,
+            query_string = &query_string
+        );
+        url
+    }
+
+    fn get_content_type_url(&self, content_type_id: &str) -> String {
+        let url = format!(
+            "{base_url}/{space_id}/environments/{environment_id}/content_types/{content_type_id}",
+            base_url = &self.base_url,
+            space_id = &self.space_id,
+            environment_id = &self.environment_id,
+            content_type_id = &content_type_id
+        );
+        url
+    }
+
+    fn get_content_types_query_string_url(&self, query_string: &str) -> String {
+        let url = format!(
+            "{base_url}/{space_id}/environments/{environment}/content_types{query_string}",
+            base_url = &self.base_url,
+            space_id = &self.space_id,
+            environment = &self.environment_id,
+            query_string = &query_string
+        );
+        url
+    }
+
+    pub async fn get_space(&self) -> Result<Option<Space>> {
+        let url = self.get_space_url();
+        let space = self
+            .http_client
+            .get_optional::<Space>(&url, &self.delivery_api_access_token)
+            .await?;
+        Ok(space)
+    }
+
+    /// Gets the locales of the environment, including which one is the default and the
+    /// fallback of each.
+    pub async fn get_locales(&self) -> Result<Vec<Locale>> {
+        let url = self.get_locales_url();
+        let collection = self
+            .http_client
+            .get::<Collection<Locale>>(&url, &self.delivery_api_access_token)
+            .await?;
+        Ok(collection.items)
+    }
+
+    /// Gets the public tags of the environment.
+    pub async fn get_tags(&self) -> Result<Vec<Tag>> {
+        let url = self.get_tags_url();
+        let collection = self
+            .http_client
+            .get::<Collection<Tag>>(&url, &self.delivery_api_access_token)
+            .await?;
+        Ok(collection.items)
+    }
+
+    pub async fn get_entry<T>(&self, entry_id: &str) -> Result<Option<T>>
     where
         for<'a> T: Serialize + Deserialize<'a>,
     {
-        if let Some(entry) = self.get_contentful_entry(entry_id).await? {
-            let mut entry_json_value = entry.fields.clone();
-            entry_json_value["sys"] = json!(entry.sys);
-            let entry_string = entry_json_value.to_string();
-            let entry = serde_json::from_str::<T>(entry_string.as_str())?;
+        if let Some(entry) = self.get_entry_json_value(entry_id).await? {
+            let entry_json_value = fields_with_sys(entry);
+            let entry = serde_json::from_value::<T>(entry_json_value)?;
             Ok(Some(entry))
         } else {
             Ok(None)
         }
     }
 
-    pub async fn get_contentful_entry(
-        &self,
-        entry_id: &str,
-    ) -> Result<Option<Entry<Value>>, Box<dyn std::error::Error>> {
+    pub async fn get_contentful_entry(&self, entry_id: &str) -> Result<Option<Entry<Value>>> {
         let url = self.get_entry_url(entry_id);
-        let json_value =
-            http_client::get::<Entry<Value>>(&url, &self.delivery_api_access_token).await?;
+        let json_value = self
+            .http_client
+            .get_optional::<Entry<Value>>(&url, &self.delivery_api_access_token)
+            .await?;
         Ok(json_value)
     }
 
-    pub async fn get_entry_json_value(
-        &self,
-        entry_id: &str,
-    ) -> Result<Option<Value>, Box<dyn std::error::Error>> {
+    pub async fn get_entry_json_value(&self, entry_id: &str) -> Result<Option<Value>> {
         let url = self.get_entry_url(entry_id);
-        let json_value = http_client::get::<Value>(&url, &self.delivery_api_access_token).await?;
+        let json_value = self
+            .http_client
+            .get_optional::<Value>(&url, &self.delivery_api_access_token)
+            .await?;
         Ok(json_value)
     }
 
-    pub async fn get_entries<T>(
+    pub async fn get_entries<T>(&self, query_builder: Option<QueryBuilder>) -> Result<Vec<T>>
+    where
+        for<'a> T: Serialize + Deserialize<'a>,
+    {
+        let query_string = if let Some(query_builder) = query_builder {
+            query_builder.build()
+        } else {
+            "".to_string()
+        };
+
+        self.get_entries_by_query_string::<T>(query_string.as_str())
+            .await
+    }
+
+    pub async fn get_entries_by_query_string<T>(&self, query_string: &str) -> Result<Vec<T>>
+    where
+        for<'a> T: Serialize + Deserialize<'a>,
+    {
+        let collection = self
+            .get_entries_collection_by_query_string::<T>(query_string)
+            .await?;
+        Ok(collection.items)
+    }
+
+    /// Gets a page of entries along with the `total`, `skip` and `limit` of the query, and the
+    /// links that could not be resolved.
+    pub async fn get_entries_collection<T>(
         &self,
         query_builder: Option<QueryBuilder>,
-    ) -> Result<Vec<T>, Box<dyn std::error::Error>>
+    ) -> Result<Collection<T>>
     where
         for<'a> T: Serialize + Deserialize<'a>,
     {
@@ -106,193 +252,383 @@ impl ContentfulClient {
             "".to_string()
         };
 
-        self.get_entries_by_query_string::<T>(query_string.as_str())
+        self.get_entries_collection_by_query_string::<T>(query_string.as_str())
             .await
     }
 
-    pub async fn get_entries_by_query_string<T>(
+    pub async fn get_entries_collection_by_query_string<T>(
         &self,
         query_string: &str,
-    ) -> Result<Vec<T>, Box<dyn std::error::Error>>
+    ) -> Result<Collection<T>>
     where
         for<'a> T: Serialize + Deserialize<'a>,
     {
         log::debug!("query_string: {:?}", &query_string);
         let url = self.get_query_string_url(query_string);
-        if let Some(json) = http_client::get::<Value>(&url, &self.delivery_api_access_token).await?
-        {
-            if let Some(items) = json.clone().get_mut("items") {
-                if items.is_array() {
-                    if let Some(includes) = json.get("includes") {
-                        self.resolve_array(items, includes)?;
-                    } else {
-                        let includes = Value::default();
-                        self.resolve_array(items, &includes)?;
-                    }
-
-                    let ar_string = items.to_string();
-                    let entries = serde_json::from_str::<Vec<T>>(ar_string.as_str())?;
-                    Ok(entries)
-                } else {
-                    unimplemented!();
-                }
-            } else {
-                unimplemented!();
+        let mut json = self
+            .http_client
+            .get::<Value>(&url, &self.delivery_api_access_token)
+            .await?;
+        let max_depth = include_depth(query_string).unwrap_or(DEFAULT_INCLUDE_DEPTH);
+        let resolver_unresolved_links =
+            resolve_response(&mut json, self.remove_unresolved, max_depth)?;
+        let mut unresolved_links = not_resolvable_links(&json["errors"]);
+        for link in resolver_unresolved_links {
+            if !unresolved_links.contains(&link) {
+                unresolved_links.push(link);
             }
-        } else {
-            unimplemented!();
         }
+
+        let mut collection = serde_json::from_value::<Collection<T>>(json)?;
+        collection.unresolved_links = unresolved_links;
+        Ok(collection)
+    }
+
+    /// Streams all entries matching the query, fetching the pages with `skip` and `limit`
+    /// as the stream is consumed until `total` is reached.
+    pub fn get_entries_stream<T>(
+        &self,
+        query_builder: QueryBuilder,
+    ) -> impl Stream<Item = Result<T>> + '_
+    where
+        for<'a> T: Serialize + Deserialize<'a> + 'static,
+    {
+        let start_skip = query_builder.skip_value().unwrap_or(0);
+        stream::try_unfold(Some(start_skip), move |skip| {
+            let query_builder = query_builder.clone();
+            async move {
+                let skip = match skip {
+                    Some(skip) => skip,
+                    None => return Ok::<_, Error>(None),
+                };
+                let collection = self
+                    .get_entries_collection::<T>(Some(query_builder.skip(skip as i32)))
+                    .await?;
+                let next_skip = skip + collection.items.len() as u32;
+                let next_skip = if collection.items.is_empty() || next_skip >= collection.total {
+                    None
+                } else {
+                    Some(next_skip)
+                };
+                Ok(Some((collection.items, next_skip)))
+            }
+        })
+        .map_ok(|items| stream::iter(items.into_iter().map(Ok)))
+        .try_flatten()
     }
 
     pub async fn get_entries_by_type<T>(
         &self,
         content_type: &str,
         query_builder: Option<QueryBuilder>,
-    ) -> Result<Vec<T>, Box<dyn std::error::Error>>
+    ) -> Result<Vec<T>>
     where
         for<'a> T: Serialize + Deserialize<'a>,
     {
         let query_builder = query_builder
-            .unwrap_or(QueryBuilder::new())
+            .unwrap_or_default()
             .content_type_is(content_type);
 
         self.get_entries(Some(query_builder)).await
         //self.get_entries_by_query_string::<T>(Some(new_query_string)).await
     }
 
-    fn resolve_array(
-        &self,
-        value: &mut Value,
-        includes: &Value,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        let items = value.as_array_mut().unwrap();
-        for item in items {
-            if item.is_object() {
-                self.resolve_object(item, includes)?;
-            } else if item.is_string() || item.is_number() {
-                // do nothing
-            } else {
-                log::error!("Unimplemented item {}", &item);
-                unimplemented!();
-            }
+    /// Gets an asset, with its `sys` properties.
+    pub async fn get_asset(&self, asset_id: &str) -> Result<Option<Asset>> {
+        let url = self.get_asset_url(asset_id);
+        if let Some(asset) = self
+            .http_client
+            .get_optional::<Value>(&url, &self.delivery_api_access_token)
+            .await?
+        {
+            let asset = serde_json::from_value::<Asset>(fields_with_sys(asset))?;
+            Ok(Some(asset))
+        } else {
+            Ok(None)
         }
-        Ok(())
     }
 
-    fn resolve_object(
-        &self,
-        value: &mut Value,
-        includes: &Value,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        if let Some(sys) = value.get("sys") {
-            if let Some(sys_type) = sys.get("type") {
-                if sys_type == "Entry" {
-                    self.resolve_entry(value, includes)?;
-                } else if sys_type == "Link" {
-                    self.resolve_link(value, includes)?;
-                } else {
-                    let node_type = value["nodeType"].clone();
-                    if node_type == "document" {
-                        log::warn!("TODO: Richtext is not yet implemented");
-                    } else {
-                        unimplemented!(
-                            "{} - {} not implemented for {}",
-                            &sys_type,
-                            &node_type,
-                            &value
-                        );
-                    }
-                }
-            } else {
-                unimplemented!("sys.type do not exist, though sys exists") // TODO: Can this ever happen?
-            }
+    /// Gets the assets matching the query, e.g. filtered with
+    /// [`QueryBuilder::mime_type_group_is`], with their `sys` properties.
+    pub async fn get_assets(&self, query_builder: Option<QueryBuilder>) -> Result<Vec<Asset>> {
+        let query_string = if let Some(query_builder) = query_builder {
+            query_builder.build()
         } else {
-            // Do nothing, as it likely a json object
+            "".to_string()
+        };
+
+        log::debug!("query_string: {:?}", &query_string);
+        let url = self.get_assets_query_string_url(&query_string);
+        let collection = self
+            .http_client
+            .get::<Collection<Value>>(&url, &self.delivery_api_access_token)
+            .await?;
+        let mut assets = Vec::new();
+        for asset in collection.items {
+            assets.push(serde_json::from_value::<Asset>(fields_with_sys(asset))?);
         }
+        Ok(assets)
+    }
 
-        Ok(())
+    pub async fn get_content_type(&self, content_type_id: &str) -> Result<Option<ContentType>> {
+        let url = self.get_content_type_url(content_type_id);
+        let content_type = self
+            .http_client
+            .get_optional::<ContentType>(&url, &self.delivery_api_access_token)
+            .await?;
+        Ok(content_type)
     }
 
-    fn resolve_entry(
+    pub async fn get_content_types(
         &self,
-        value: &mut Value,
-        includes: &Value,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        if let Some(fields) = value.get_mut("fields") {
-            if fields.is_object() {
-                let entry_object = fields.as_object_mut().unwrap();
-                for (_field_name, field_value) in entry_object {
-                    if field_value.is_object() {
-                        self.resolve_object(field_value, includes)?;
-                    } else if field_value.is_array() {
-                        self.resolve_array(field_value, includes)?;
-                    } else {
-                        // Regular string, number, etc, values. No need to do anything.
-                    }
-                }
-                *value = fields.clone();
-            } else {
-                unimplemented!();
-            }
+        query_builder: Option<QueryBuilder>,
+    ) -> Result<Vec<ContentType>> {
+        let query_string = if let Some(query_builder) = query_builder {
+            query_builder.build()
         } else {
-            unimplemented!();
-        }
+            "".to_string()
+        };
 
-        Ok(())
+        let url = self.get_content_types_query_string_url(&query_string);
+        let collection = self
+            .http_client
+            .get::<Collection<ContentType>>(&url, &self.delivery_api_access_token)
+            .await?;
+        Ok(collection.items)
     }
 
-    fn resolve_asset(&self, value: &mut Value) -> Result<(), Box<dyn std::error::Error>> {
-        if let Some(fields) = value.get_mut("fields") {
-            if fields.is_object() {
-                *value = fields.clone();
+    /// Synchronizes all entries and assets of the environment.
+    ///
+    /// Without a sync token an initial sync is performed. Pass the `next_sync_token` of a
+    /// previous result to get only the entries and assets changed or deleted since then.
+    /// All pages of the sync are fetched before returning.
+    pub async fn sync(&self, sync_token: Option<&str>) -> Result<SyncResult> {
+        let mut query_string = match sync_token {
+            Some(sync_token) => sync_query_string(sync_token),
+            None => "?initial=true".to_string(),
+        };
+        let mut items = Vec::new();
+
+        loop {
+            let url = self.get_sync_url(&query_string);
+            let page = self
+                .http_client
+                .get::<SyncPage>(&url, &self.delivery_api_access_token)
+                .await?;
+
+            for item in page.items {
+                items.push(sync_item_from_value(item)?);
+            }
+
+            if let Some(next_page_url) = page.next_page_url {
+                query_string = sync_query_string(&sync_token_from_url(&next_page_url)?);
+            } else if let Some(next_sync_url) = page.next_sync_url {
+                let next_sync_token = sync_token_from_url(&next_sync_url)?;
+                return Ok(SyncResult {
+                    items,
+                    next_sync_token,
+                });
             } else {
-                unimplemented!();
+                return Err(Error::InvalidResponse(
+                    "neither nextPageUrl nor nextSyncUrl exist".into(),
+                ));
             }
-        } else {
-            unimplemented!();
         }
+    }
 
-        Ok(())
+    fn get_sync_url(&self, query_string: &str) -> String {
+        let url = format!(
+            "{base_url}/{space_id}/environments/{environment}/sync{query_string}",
+            base_url = &self.base_url,
+            space_id = &self.space_id,
+            environment = &self.environment_id,
+            query_string = &query_string
+        );
+        url
     }
+}
 
-    fn resolve_link(
-        &self,
-        value: &mut Value,
-        includes: &Value,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        let link_type = value["sys"]["linkType"].clone();
-        let link_id = value["sys"]["id"].clone();
-        if link_type == "Entry" {
-            let included_entries = includes["Entry"].as_array().unwrap();
-            let mut filtered_entries = included_entries
+/// Returns the `include` parameter of a query string.
+fn include_depth(query_string: &str) -> Option<u32> {
+    query_string
+        .trim_start_matches('?')
+        .split('&')
+        .find_map(|parameter| parameter.strip_prefix("include="))
+        .and_then(|depth| depth.parse().ok())
+}
+
+/// Returns the links of the `notResolvable` errors of a response.
+fn not_resolvable_links(errors: &Value) -> Vec<LinkReference> {
+    errors
+        .as_array()
+        .map(|errors| {
+            errors
                 .iter()
-                .filter(|entry| entry["sys"]["id"] == link_id)
-                .take(1);
-            let linked_entry = filtered_entries.next();
-            if let Some(entry) = linked_entry {
-                let mut entry = entry.clone();
-                self.resolve_entry(&mut entry, includes)?;
-                *value = entry;
-                //value["fields"] = entry["fields"].clone();
-                //*value = entry["fields"].clone();
+                .filter(|error| error["sys"]["id"] == "notResolvable")
+                .filter_map(|error| {
+                    let details = &error["details"];
+                    Some(LinkReference::new(
+                        details["id"].as_str()?,
+                        details["linkType"].as_str()?,
+                    ))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SyncPage {
+    items: Vec<Value>,
+    next_page_url: Option<String>,
+    next_sync_url: Option<String>,
+}
+
+/// The query string of a sync with the token percent-encoded, as tokens may contain e.g. `+`.
+fn sync_query_string(sync_token: &str) -> String {
+    QueryBuilder::new()
+        .add_query_param("sync_token", sync_token)
+        .build()
+}
+
+fn sync_token_from_url(url: &str) -> Result<String> {
+    let url = reqwest::Url::parse(url)
+        .map_err(|_| Error::InvalidResponse(format!("invalid sync url {}", url)))?;
+    url.query_pairs()
+        .find(|(key, _)| key == "sync_token")
+        .map(|(_, sync_token)| sync_token.into_owned())
+        .ok_or_else(|| Error::InvalidResponse(format!("sync_token do not exist in {}", &url)))
+}
+
+fn sync_item_from_value(mut item: Value) -> Result<SyncItem> {
+    let sys_type = item["sys"]["type"].as_str().unwrap_or_default().to_string();
+    match sys_type.as_str() {
+        "Entry" | "Asset" => {
+            if item.get("fields").is_none() {
+                item["fields"] = json!({});
             }
-        } else if link_type == "Asset" {
-            let included_assets = includes["Asset"].as_array().unwrap();
-            let mut filtered_assets = included_assets
-                .iter()
-                .filter(|entry| entry["sys"]["id"] == link_id)
-                .take(1);
-            let linked_asset = filtered_assets.next();
-            if let Some(asset) = linked_asset {
-                let mut asset = asset.clone();
-                self.resolve_asset(&mut asset)?;
-                *value = asset;
+            let entry = serde_json::from_value::<Entry<Value>>(item)?;
+            if sys_type == "Entry" {
+                Ok(SyncItem::Entry(entry))
+            } else {
+                Ok(SyncItem::Asset(entry))
             }
-        } else {
-            unimplemented!();
         }
+        "DeletedEntry" => Ok(SyncItem::DeletedEntry(serde_json::from_value::<
+            SystemProperties,
+        >(item["sys"].take())?)),
+        "DeletedAsset" => Ok(SyncItem::DeletedAsset(serde_json::from_value::<
+            SystemProperties,
+        >(item["sys"].take())?)),
+        _ => Err(Error::InvalidResponse(format!(
+            "Unexpected sync item {}",
+            &item
+        ))),
+    }
+}
+
+/// Builds a [`ContentfulClient`] with custom HTTP settings.
+///
+/// All requests of the built client share a single `reqwest::Client`, and so its
+/// connection pool.
+pub struct ContentfulClientBuilder {
+    delivery_api_access_token: String,
+    space_id: String,
+    base_url: Option<String>,
+    environment_id: String,
+    preview: bool,
+    remove_unresolved: Option<bool>,
+    client: Option<reqwest::Client>,
+    timeout: Option<Duration>,
+    user_agent: Option<String>,
+    retry_policy: RetryPolicy,
+}
+
+impl ContentfulClientBuilder {
+    pub fn new(delivery_api_access_token: &str, space_id: &str) -> ContentfulClientBuilder {
+        ContentfulClientBuilder {
+            delivery_api_access_token: delivery_api_access_token.into(),
+            space_id: space_id.into(),
+            base_url: None,
+            environment_id: "master".into(),
+            preview: false,
+            remove_unresolved: None,
+            client: None,
+            timeout: None,
+            user_agent: None,
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+
+    /// Sets the API host, e.g. [`ContentfulClient::EU_BASE_URL`] or a local mock server.
+    pub fn base_url(mut self, base_url: &str) -> ContentfulClientBuilder {
+        self.base_url = Some(base_url.trim_end_matches('/').into());
+        self
+    }
+
+    /// Uses the Content Preview API, unless a base URL is set. Expects a preview access token.
+    pub fn preview(mut self, preview: bool) -> ContentfulClientBuilder {
+        self.preview = preview;
+        self
+    }
+
+    /// Removes links that cannot be resolved from the results: they are dropped from
+    /// arrays and set to null in single reference fields. Defaults to true for preview.
+    pub fn remove_unresolved(mut self, remove_unresolved: bool) -> ContentfulClientBuilder {
+        self.remove_unresolved = Some(remove_unresolved);
+        self
+    }
+
+    pub fn environment(mut self, environment_id: &str) -> ContentfulClientBuilder {
+        self.environment_id = environment_id.into();
+        self
+    }
 
-        //*value = value["fields"].clone();
-        Ok(())
+    /// Uses the given client, e.g. one configured with a proxy or default headers,
+    /// instead of creating a new one.
+    pub fn client(mut self, client: reqwest::Client) -> ContentfulClientBuilder {
+        self.client = Some(client);
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> ContentfulClientBuilder {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn user_agent(mut self, user_agent: &str) -> ContentfulClientBuilder {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Sets the policy used to retry rate limited and failed requests.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> ContentfulClientBuilder {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    pub fn build(self) -> ContentfulClient {
+        let preview = self.preview;
+        let base_url = self.base_url.unwrap_or_else(|| {
+            if preview {
+                ContentfulClient::PREVIEW_BASE_URL.into()
+            } else {
+                ContentfulClient::BASE_URL.into()
+            }
+        });
+        ContentfulClient {
+            base_url: format!("{}/spaces", &base_url),
+            delivery_api_access_token: self.delivery_api_access_token,
+            space_id: self.space_id,
+            environment_id: self.environment_id,
+            remove_unresolved: self.remove_unresolved.unwrap_or(self.preview),
+            http_client: HttpClient::new(
+                self.client,
+                self.user_agent,
+                self.timeout,
+                self.retry_policy,
+            ),
+        }
     }
 }
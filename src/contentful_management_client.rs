@@ -1,24 +1,38 @@
-use crate::{http_client, models::Entry};
+use crate::error::{Error, Result};
+use crate::http_client::HttpClient;
+use crate::models::{AssetFields, Collection, ContentType, Entry, Upload};
+use crate::query_builder::QueryBuilder;
+use crate::retry_policy::RetryPolicy;
 use serde::{de::DeserializeOwned, Serialize};
 use serde_json::json;
 use serde_json::Value;
+use std::time::{Duration, Instant};
 
+/// How often `wait_for_asset_file` checks whether the file of an asset is processed.
+const ASSET_PROCESSING_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Clone)]
 pub struct ContentfulManagementClient {
     management_api_access_token: String,
     space_id: String,
     base_url: String,
+    upload_base_url: String,
     environment_id: String,
+    http_client: HttpClient,
 }
 
 impl ContentfulManagementClient {
+    /// The Content Management API host.
+    pub const BASE_URL: &str = "https://api.contentful.com";
+    /// The Content Management API host for spaces with EU data residency.
+    pub const EU_BASE_URL: &str = "https://api.eu.contentful.com";
+    /// The Upload API host.
+    pub const UPLOAD_BASE_URL: &str = "https://upload.contentful.com";
+    /// The Upload API host for spaces with EU data residency.
+    pub const EU_UPLOAD_BASE_URL: &str = "https://upload.eu.contentful.com";
+
     pub fn new(management_api_access_token: &str, space_id: &str) -> ContentfulManagementClient {
-        let environment_id = "master".into();
-        ContentfulManagementClient {
-            base_url: "https://api.contentful.com/spaces".into(),
-            management_api_access_token: management_api_access_token.into(),
-            space_id: space_id.into(),
-            environment_id,
-        }
+        ContentfulManagementClient::builder(management_api_access_token, space_id).build()
     }
 
     pub fn with_environment<S>(
@@ -26,12 +40,16 @@ impl ContentfulManagementClient {
         space_id: &str,
         environment_id: &str,
     ) -> ContentfulManagementClient {
-        ContentfulManagementClient {
-            base_url: "https://api.contentful.com/spaces".into(),
-            management_api_access_token: management_api_access_token.into(),
-            space_id: space_id.into(),
-            environment_id: environment_id.into(),
-        }
+        ContentfulManagementClient::builder(management_api_access_token, space_id)
+            .environment(environment_id)
+            .build()
+    }
+
+    pub fn builder(
+        management_api_access_token: &str,
+        space_id: &str,
+    ) -> ContentfulManagementClientBuilder {
+        ContentfulManagementClientBuilder::new(management_api_access_token, space_id)
     }
 
     fn get_entry_url(&self, entry_id: &str) -> String {
@@ -54,13 +72,59 @@ impl ContentfulManagementClient {
         url
     }
 
-    pub async fn get_entry(
-        &self,
-        entry_id: &str,
-    ) -> Result<Option<Entry<Value>>, Box<dyn std::error::Error>> {
+    fn get_asset_url(&self, asset_id: &str) -> String {
+        format!(
+            "{base_url}/{space_id}/environments/{environment_id}/assets/{asset_id}",
+            base_url = &self.base_url,
+            space_id = &self.space_id,
+            environment_id = &self.environment_id,
+            asset_id = &asset_id
+        )
+    }
+
+    fn get_assets_url(&self) -> String {
+        format!(
+            "{base_url}/{space_id}/environments/{environment_id}/assets",
+            base_url = &self.base_url,
+            space_id = &self.space_id,
+            environment_id = &self.environment_id,
+        )
+    }
+
+    fn get_content_type_url(&self, content_type_id: &str) -> String {
+        format!(
+            "{base_url}/{space_id}/environments/{environment_id}/content_types/{content_type_id}",
+            base_url = &self.base_url,
+            space_id = &self.space_id,
+            environment_id = &self.environment_id,
+            content_type_id = &content_type_id
+        )
+    }
+
+    fn get_content_types_query_string_url(&self, query_string: &str) -> String {
+        format!(
+            "{base_url}/{space_id}/environments/{environment_id}/content_types{query_string}",
+            base_url = &self.base_url,
+            space_id = &self.space_id,
+            environment_id = &self.environment_id,
+            query_string = &query_string
+        )
+    }
+
+    fn get_uploads_url(&self) -> String {
+        format!(
+            "{upload_base_url}/{space_id}/uploads",
+            upload_base_url = &self.upload_base_url,
+            space_id = &self.space_id,
+        )
+    }
+
+    pub async fn get_entry(&self, entry_id: &str) -> Result<Option<Entry<Value>>> {
         let url = self.get_entry_url(entry_id);
-        let entry =
-            http_client::get::<Entry<Value>>(&url, &self.management_api_access_token).await?;
+        let entry = self
+            .http_client
+            .get_optional::<Entry<Value>>(&url, &self.management_api_access_token)
+            .await?;
         Ok(entry)
     }
 
@@ -68,51 +132,50 @@ impl ContentfulManagementClient {
         &self,
         entry_id: &str,
         locale: &str,
-    ) -> Result<Option<Entry<T>>, Box<dyn std::error::Error>>
+    ) -> Result<Option<Entry<T>>>
     where
         T: DeserializeOwned + Serialize,
     {
         let url = self.get_entry_url(entry_id);
-        if let Some(entry_json) =
-            http_client::get::<Entry<Value>>(&url, &self.management_api_access_token).await?
+        if let Some(entry_json) = self
+            .http_client
+            .get_optional::<Entry<Value>>(&url, &self.management_api_access_token)
+            .await?
         {
             let entry_typed =
                 helpers::convert_json_object_to_typed_entry(entry_json.fields.clone(), locale)?;
-            let entry = Entry::new(entry_typed, entry_json.sys.clone());
+            let mut entry = Entry::new(entry_typed, entry_json.sys.clone());
+            entry.metadata = entry_json.metadata;
             Ok(Some(entry))
         } else {
             Ok(None)
         }
     }
 
-    pub async fn create_entry_from_json<T>(
-        &self,
-        entry: &Value,
-        content_type_id: &str,
-    ) -> Result<T, Box<dyn std::error::Error>>
+    pub async fn create_entry_from_json<T>(&self, entry: &Value, content_type_id: &str) -> Result<T>
     where
         T: DeserializeOwned,
     {
         let url = self.get_entries_url();
-        let mut json = http_client::post(
-            &url,
-            &self.management_api_access_token,
-            content_type_id,
-            entry,
-        )
-        .await?;
-        let entry_created_fields = json.get_mut("fields").unwrap();
+        let mut json = self
+            .http_client
+            .post(
+                &url,
+                &self.management_api_access_token,
+                Some(content_type_id),
+                entry,
+            )
+            .await?;
+        let entry_created_fields = json
+            .get_mut("fields")
+            .ok_or_else(|| Error::InvalidResponse("fields do not exist".into()))?;
         let entry_created_string = entry_created_fields.to_string();
         let entry_created = serde_json::from_str::<T>(&entry_created_string)?;
 
         Ok(entry_created)
     }
 
-    pub async fn create_entry<T>(
-        &self,
-        entry: &T,
-        content_type_id: &str,
-    ) -> Result<T, Box<dyn std::error::Error>>
+    pub async fn create_entry<T>(&self, entry: &T, content_type_id: &str) -> Result<T>
     where
         T: DeserializeOwned + Serialize,
     {
@@ -126,7 +189,7 @@ impl ContentfulManagementClient {
         entry: &T,
         content_type_id: &str,
         locale: &str,
-    ) -> Result<T, Box<dyn std::error::Error>>
+    ) -> Result<T>
     where
         T: DeserializeOwned + Serialize,
     {
@@ -145,16 +208,18 @@ impl ContentfulManagementClient {
         entry_id: &str,
         version: &Option<i32>,
         content_type_id: &str,
-    ) -> Result<Value, Box<dyn std::error::Error>> {
+    ) -> Result<Value> {
         let url = self.get_entry_url(entry_id);
-        let json = http_client::put(
-            &url,
-            &self.management_api_access_token,
-            version,
-            content_type_id,
-            entry,
-        )
-        .await?;
+        let json = self
+            .http_client
+            .put(
+                &url,
+                &self.management_api_access_token,
+                version,
+                Some(content_type_id),
+                entry,
+            )
+            .await?;
         Ok(json)
     }
 
@@ -163,7 +228,7 @@ impl ContentfulManagementClient {
         entry: &Entry<Value>,
         id: &str,
         content_type_id: &str,
-    ) -> Result<Entry<Value>, Box<dyn std::error::Error>> {
+    ) -> Result<Entry<Value>> {
         let entry_updated = self
             .create_or_update_entry_from_json(
                 &json!(entry),
@@ -183,30 +248,437 @@ impl ContentfulManagementClient {
         id: &str,
         locale: &str,
         content_type_id: &str,
-    ) -> Result<Entry<T>, Box<dyn std::error::Error>>
+    ) -> Result<Entry<T>>
     where
         T: DeserializeOwned + Serialize,
     {
         let entry_json = helpers::reconstruct_json_object_with_locale(&entry.fields, locale)?;
-        let entry_to_update = Entry::new(entry_json, entry.sys.clone());
+        let mut entry_to_update = Entry::new(entry_json, entry.sys.clone());
+        entry_to_update.metadata = entry.metadata.clone();
         let updated_entry_json = self
             .create_or_update_entry(&entry_to_update, id, content_type_id)
             .await?;
         let updated_entry_typed =
             helpers::convert_json_object_to_typed_entry(json!(updated_entry_json.fields), locale)?;
-        let updated_entry = Entry::new(updated_entry_typed, updated_entry_json.sys);
+        let mut updated_entry = Entry::new(updated_entry_typed, updated_entry_json.sys);
+        updated_entry.metadata = updated_entry_json.metadata;
         Ok(updated_entry)
     }
+
+    /// Publishes the entry, which must be at `version`, i.e. its current `sys.version`.
+    pub async fn publish_entry(&self, entry_id: &str, version: i32) -> Result<Entry<Value>> {
+        let url = format!("{}/published", self.get_entry_url(entry_id));
+        self.put_version(&url, version).await
+    }
+
+    /// Unpublishes the entry, which then is a draft again.
+    pub async fn unpublish_entry(&self, entry_id: &str, version: i32) -> Result<Entry<Value>> {
+        let url = format!("{}/published", self.get_entry_url(entry_id));
+        self.delete_returning(&url, version).await
+    }
+
+    /// Archives the entry, which must not be published.
+    pub async fn archive_entry(&self, entry_id: &str, version: i32) -> Result<Entry<Value>> {
+        let url = format!("{}/archived", self.get_entry_url(entry_id));
+        self.put_version(&url, version).await
+    }
+
+    /// Unarchives the entry, which then is a draft again.
+    pub async fn unarchive_entry(&self, entry_id: &str, version: i32) -> Result<Entry<Value>> {
+        let url = format!("{}/archived", self.get_entry_url(entry_id));
+        self.delete_returning(&url, version).await
+    }
+
+    /// Deletes the entry, which must not be published.
+    pub async fn delete_entry(&self, entry_id: &str, version: i32) -> Result<()> {
+        let url = self.get_entry_url(entry_id);
+        self.http_client
+            .delete(&url, &self.management_api_access_token, Some(version))
+            .await?;
+        Ok(())
+    }
+
+    /// Uploads a file to the Upload API, to create an asset from with
+    /// [`AssetFile::from_upload`](crate::models::AssetFile::from_upload).
+    pub async fn upload(&self, data: Vec<u8>) -> Result<Upload> {
+        let url = self.get_uploads_url();
+        let json = self
+            .http_client
+            .post_bytes(&url, &self.management_api_access_token, data)
+            .await?;
+        let upload = serde_json::from_value::<Upload>(json)?;
+        Ok(upload)
+    }
+
+    pub async fn get_asset(&self, asset_id: &str) -> Result<Option<Entry<AssetFields>>> {
+        let url = self.get_asset_url(asset_id);
+        let asset = self
+            .http_client
+            .get_optional::<Entry<AssetFields>>(&url, &self.management_api_access_token)
+            .await?;
+        Ok(asset)
+    }
+
+    /// Creates an asset with a generated id. Its files still need to be processed with
+    /// [`process_asset_file`](Self::process_asset_file).
+    pub async fn create_asset(&self, fields: &AssetFields) -> Result<Entry<AssetFields>> {
+        let url = self.get_assets_url();
+        let json = self
+            .http_client
+            .post(
+                &url,
+                &self.management_api_access_token,
+                None,
+                &json!({ "fields": fields }),
+            )
+            .await?;
+        let asset = serde_json::from_value::<Entry<AssetFields>>(json)?;
+        Ok(asset)
+    }
+
+    /// Creates the asset with the given id, or updates it if `asset.sys.version` is set.
+    pub async fn create_or_update_asset(
+        &self,
+        asset: &Entry<AssetFields>,
+        asset_id: &str,
+    ) -> Result<Entry<AssetFields>> {
+        let url = self.get_asset_url(asset_id);
+        let json = self
+            .http_client
+            .put(
+                &url,
+                &self.management_api_access_token,
+                &asset.sys.version,
+                None,
+                &json!(asset),
+            )
+            .await?;
+        let asset = serde_json::from_value::<Entry<AssetFields>>(json)?;
+        Ok(asset)
+    }
+
+    /// Starts processing the file of the asset for the locale, which sets its `url` once done,
+    /// see [`wait_for_asset_file`](Self::wait_for_asset_file).
+    pub async fn process_asset_file(
+        &self,
+        asset_id: &str,
+        locale: &str,
+        version: i32,
+    ) -> Result<()> {
+        let url = format!(
+            "{asset_url}/files/{locale}/process",
+            asset_url = self.get_asset_url(asset_id),
+            locale = locale
+        );
+        self.http_client
+            .put_version(&url, &self.management_api_access_token, version)
+            .await?;
+        Ok(())
+    }
+
+    /// Polls the asset until its file for the locale is processed and returns the asset, which
+    /// then has a new version to publish it with. Fails with [`Error::AssetNotProcessed`] if the
+    /// file is not processed within `timeout`.
+    pub async fn wait_for_asset_file(
+        &self,
+        asset_id: &str,
+        locale: &str,
+        timeout: Duration,
+    ) -> Result<Entry<AssetFields>> {
+        let started = Instant::now();
+        loop {
+            let asset = self.get_asset(asset_id).await?.ok_or_else(|| {
+                Error::InvalidResponse(format!("asset {} does not exist", asset_id))
+            })?;
+            if matches!(asset.fields.file.get(locale), Some(file) if file.is_processed()) {
+                return Ok(asset);
+            }
+            if started.elapsed() + ASSET_PROCESSING_POLL_INTERVAL > timeout {
+                return Err(Error::AssetNotProcessed {
+                    asset_id: asset_id.into(),
+                    locale: locale.into(),
+                });
+            }
+            log::debug!("Waiting for file of asset {} for {}", asset_id, locale);
+            tokio::time::sleep(ASSET_PROCESSING_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Publishes the asset, whose files must be processed.
+    pub async fn publish_asset(&self, asset_id: &str, version: i32) -> Result<Entry<AssetFields>> {
+        let url = format!("{}/published", self.get_asset_url(asset_id));
+        self.put_version(&url, version).await
+    }
+
+    /// Unpublishes the asset, which then is a draft again.
+    pub async fn unpublish_asset(
+        &self,
+        asset_id: &str,
+        version: i32,
+    ) -> Result<Entry<AssetFields>> {
+        let url = format!("{}/published", self.get_asset_url(asset_id));
+        self.delete_returning(&url, version).await
+    }
+
+    /// Deletes the asset, which must not be published.
+    pub async fn delete_asset(&self, asset_id: &str, version: i32) -> Result<()> {
+        let url = self.get_asset_url(asset_id);
+        self.http_client
+            .delete(&url, &self.management_api_access_token, Some(version))
+            .await?;
+        Ok(())
+    }
+
+    /// Gets the content type, including unpublished changes.
+    pub async fn get_content_type(&self, content_type_id: &str) -> Result<Option<ContentType>> {
+        let url = self.get_content_type_url(content_type_id);
+        let content_type = self
+            .http_client
+            .get_optional::<ContentType>(&url, &self.management_api_access_token)
+            .await?;
+        Ok(content_type)
+    }
+
+    pub async fn get_content_types(
+        &self,
+        query_builder: Option<QueryBuilder>,
+    ) -> Result<Vec<ContentType>> {
+        let query_string = if let Some(query_builder) = query_builder {
+            query_builder.build()
+        } else {
+            "".to_string()
+        };
+
+        let url = self.get_content_types_query_string_url(&query_string);
+        let collection = self
+            .http_client
+            .get::<Collection<ContentType>>(&url, &self.management_api_access_token)
+            .await?;
+        Ok(collection.items)
+    }
+
+    /// Creates the content type with a generated id, ignoring `content_type.sys`.
+    pub async fn create_content_type(&self, content_type: &ContentType) -> Result<ContentType> {
+        let url = self.get_content_types_query_string_url("");
+        let json = self
+            .http_client
+            .post(
+                &url,
+                &self.management_api_access_token,
+                None,
+                &helpers::content_type_body(content_type),
+            )
+            .await?;
+        let created = serde_json::from_value::<ContentType>(json)?;
+        Ok(created)
+    }
+
+    /// Creates the content type with the id `content_type.sys.id`, or updates it if
+    /// `content_type.sys.version` is set. Changes only apply to entries once the content type is
+    /// activated.
+    pub async fn create_or_update_content_type(
+        &self,
+        content_type: &ContentType,
+    ) -> Result<ContentType> {
+        let url = self.get_content_type_url(&content_type.sys.id);
+        let json = self
+            .http_client
+            .put(
+                &url,
+                &self.management_api_access_token,
+                &content_type.sys.version,
+                None,
+                &helpers::content_type_body(content_type),
+            )
+            .await?;
+        let updated = serde_json::from_value::<ContentType>(json)?;
+        Ok(updated)
+    }
+
+    /// Activates, i.e. publishes, the content type, so that entries can use its fields.
+    pub async fn activate_content_type(
+        &self,
+        content_type_id: &str,
+        version: i32,
+    ) -> Result<ContentType> {
+        let url = format!("{}/published", self.get_content_type_url(content_type_id));
+        self.put_version(&url, version).await
+    }
+
+    /// Deactivates, i.e. unpublishes, the content type, which must not have entries.
+    pub async fn deactivate_content_type(
+        &self,
+        content_type_id: &str,
+        version: i32,
+    ) -> Result<ContentType> {
+        let url = format!("{}/published", self.get_content_type_url(content_type_id));
+        self.delete_returning(&url, version).await
+    }
+
+    /// Same as [`activate_content_type`](Self::activate_content_type).
+    pub async fn publish_content_type(
+        &self,
+        content_type_id: &str,
+        version: i32,
+    ) -> Result<ContentType> {
+        self.activate_content_type(content_type_id, version).await
+    }
+
+    /// Same as [`deactivate_content_type`](Self::deactivate_content_type).
+    pub async fn unpublish_content_type(
+        &self,
+        content_type_id: &str,
+        version: i32,
+    ) -> Result<ContentType> {
+        self.deactivate_content_type(content_type_id, version).await
+    }
+
+    /// Deletes the content type, which must be deactivated.
+    pub async fn delete_content_type(&self, content_type_id: &str, version: i32) -> Result<()> {
+        let url = self.get_content_type_url(content_type_id);
+        self.http_client
+            .delete(&url, &self.management_api_access_token, Some(version))
+            .await?;
+        Ok(())
+    }
+
+    /// Puts with the version only, which returns the resource in its new state.
+    async fn put_version<T>(&self, url: &str, version: i32) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        let json = self
+            .http_client
+            .put_version(url, &self.management_api_access_token, version)
+            .await?
+            .ok_or_else(|| Error::InvalidResponse(format!("{} returned no body", url)))?;
+        let resource = serde_json::from_value::<T>(json)?;
+        Ok(resource)
+    }
+
+    /// Deletes a state, e.g. published, which returns the resource in its new state.
+    async fn delete_returning<T>(&self, url: &str, version: i32) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        let json = self
+            .http_client
+            .delete(url, &self.management_api_access_token, Some(version))
+            .await?
+            .ok_or_else(|| Error::InvalidResponse(format!("{} returned no body", url)))?;
+        let resource = serde_json::from_value::<T>(json)?;
+        Ok(resource)
+    }
+}
+
+/// Builds a [`ContentfulManagementClient`] with custom HTTP settings.
+///
+/// All requests of the built client share a single `reqwest::Client`, and so its
+/// connection pool.
+pub struct ContentfulManagementClientBuilder {
+    management_api_access_token: String,
+    space_id: String,
+    base_url: String,
+    upload_base_url: String,
+    environment_id: String,
+    client: Option<reqwest::Client>,
+    timeout: Option<Duration>,
+    user_agent: Option<String>,
+    retry_policy: RetryPolicy,
+}
+
+impl ContentfulManagementClientBuilder {
+    pub fn new(
+        management_api_access_token: &str,
+        space_id: &str,
+    ) -> ContentfulManagementClientBuilder {
+        ContentfulManagementClientBuilder {
+            management_api_access_token: management_api_access_token.into(),
+            space_id: space_id.into(),
+            base_url: ContentfulManagementClient::BASE_URL.into(),
+            upload_base_url: ContentfulManagementClient::UPLOAD_BASE_URL.into(),
+            environment_id: "master".into(),
+            client: None,
+            timeout: None,
+            user_agent: None,
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+
+    /// Sets the API host, e.g. [`ContentfulManagementClient::EU_BASE_URL`] or a local mock server.
+    pub fn base_url(mut self, base_url: &str) -> ContentfulManagementClientBuilder {
+        self.base_url = base_url.trim_end_matches('/').into();
+        self
+    }
+
+    /// Sets the Upload API host, e.g. [`ContentfulManagementClient::EU_UPLOAD_BASE_URL`] or a
+    /// local mock server.
+    pub fn upload_base_url(mut self, upload_base_url: &str) -> ContentfulManagementClientBuilder {
+        self.upload_base_url = upload_base_url.trim_end_matches('/').into();
+        self
+    }
+
+    pub fn environment(mut self, environment_id: &str) -> ContentfulManagementClientBuilder {
+        self.environment_id = environment_id.into();
+        self
+    }
+
+    /// Uses the given client, e.g. one configured with a proxy or default headers,
+    /// instead of creating a new one.
+    pub fn client(mut self, client: reqwest::Client) -> ContentfulManagementClientBuilder {
+        self.client = Some(client);
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> ContentfulManagementClientBuilder {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn user_agent(mut self, user_agent: &str) -> ContentfulManagementClientBuilder {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Sets the policy used to retry rate limited and failed requests.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> ContentfulManagementClientBuilder {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    pub fn build(self) -> ContentfulManagementClient {
+        ContentfulManagementClient {
+            base_url: format!("{}/spaces", &self.base_url),
+            upload_base_url: format!("{}/spaces", &self.upload_base_url),
+            management_api_access_token: self.management_api_access_token,
+            space_id: self.space_id,
+            environment_id: self.environment_id,
+            http_client: HttpClient::new(
+                self.client,
+                self.user_agent,
+                self.timeout,
+                self.retry_policy,
+            ),
+        }
+    }
 }
 
 mod helpers {
+    use crate::error::{Error, Result};
+    use crate::models::ContentType;
     use serde::{de::DeserializeOwned, Serialize};
     use serde_json::{json, Value};
 
-    pub fn reconstruct_json_object_with_locale<T>(
-        entry: &T,
-        locale: &str,
-    ) -> Result<Value, Box<dyn std::error::Error>>
+    /// The content type without its `sys`, which is set by Contentful and the URL.
+    pub fn content_type_body(content_type: &ContentType) -> Value {
+        let mut body = json!(content_type);
+        if let Some(body) = body.as_object_mut() {
+            body.remove("sys");
+        }
+        body
+    }
+
+    pub fn reconstruct_json_object_with_locale<T>(entry: &T, locale: &str) -> Result<Value>
     where
         T: Serialize,
     {
@@ -225,16 +697,15 @@ mod helpers {
                 //}
             }
         } else {
-            unimplemented!();
+            return Err(Error::InvalidInput(
+                "entry must serialize to a JSON object".into(),
+            ));
         }
 
         Ok(json!(fields_map))
     }
 
-    pub fn convert_json_object_to_typed_entry<T>(
-        entry_json: Value,
-        locale: &str,
-    ) -> Result<T, Box<dyn std::error::Error>>
+    pub fn convert_json_object_to_typed_entry<T>(entry_json: Value, locale: &str) -> Result<T>
     where
         T: DeserializeOwned,
     {
@@ -243,17 +714,19 @@ mod helpers {
         if entry_json.is_object() {
             let entry_object = entry_json.as_object().unwrap();
             for (field_name, field_value) in entry_object {
-                if field_value.is_object() {
-                    entry_created_map
-                        .insert(field_name.into(), field_value.get(locale).unwrap().clone());
-                } else if field_value.is_array() {
-                    todo!();
+                if let Some(localized_value) = field_value.as_object() {
+                    if let Some(value) = localized_value.get(locale) {
+                        entry_created_map.insert(field_name.into(), value.clone());
+                    }
                 } else {
-                    todo!();
+                    return Err(Error::InvalidResponse(format!(
+                        "field {} is not localized",
+                        field_name
+                    )));
                 }
             }
         } else {
-            todo!();
+            return Err(Error::InvalidResponse("fields is not an object".into()));
         }
 
         let entry_string = json!(entry_created_map).to_string();
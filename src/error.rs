@@ -0,0 +1,166 @@
+use reqwest::StatusCode;
+use serde_json::Value;
+use std::fmt;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Errors returned by the Contentful clients.
+#[derive(Debug)]
+pub enum Error {
+    /// The request could not be sent or the response could not be read.
+    Transport(reqwest::Error),
+    /// The access token is missing, invalid or lacks the required permissions (401 / 403).
+    Authentication(Box<ApiError>),
+    /// The requested resource does not exist (404).
+    NotFound(Box<ApiError>),
+    /// The payload was rejected by Contentful (400 / 422).
+    Validation {
+        error: Box<ApiError>,
+        /// The `details.errors` array returned by Contentful.
+        errors: Vec<Value>,
+    },
+    /// The `X-Contentful-Version` sent does not match the current version (409).
+    VersionConflict(Box<ApiError>),
+    /// Too many requests (429).
+    RateLimited {
+        error: Box<ApiError>,
+        /// Seconds until the rate limit resets, from `X-Contentful-RateLimit-Reset`.
+        reset: Option<u64>,
+    },
+    /// Any other non-success response.
+    Api(Box<ApiError>),
+    /// The response could not be deserialized into the requested type.
+    Deserialization(serde_json::Error),
+    /// The response did not have the expected shape.
+    InvalidResponse(String),
+    /// The arguments cannot be sent to Contentful, e.g. an entry that does not serialize to a
+    /// JSON object.
+    InvalidInput(String),
+    /// The file of an asset was not processed in time, e.g. because the upload is not a file
+    /// Contentful can process.
+    AssetNotProcessed { asset_id: String, locale: String },
+}
+
+/// Error details returned by the Contentful API.
+#[derive(Clone, Debug)]
+pub struct ApiError {
+    pub status: StatusCode,
+    /// The error `sys.id`, e.g. `NotFound` or `VersionMismatch`.
+    pub id: Option<String>,
+    pub message: Option<String>,
+    pub request_id: Option<String>,
+    pub details: Option<Value>,
+}
+
+impl ApiError {
+    pub(crate) fn from_body(status: StatusCode, body: &str) -> ApiError {
+        let json = serde_json::from_str::<Value>(body).unwrap_or_default();
+        let as_string = |value: &Value| value.as_str().map(String::from);
+        ApiError {
+            status,
+            id: as_string(&json["sys"]["id"]),
+            message: json["message"]
+                .as_str()
+                .map(String::from)
+                .or_else(|| (!body.is_empty()).then(|| body.to_string())),
+            request_id: as_string(&json["requestId"]),
+            details: json.get("details").cloned(),
+        }
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.status)?;
+        if let Some(id) = &self.id {
+            write!(f, " {}", id)?;
+        }
+        if let Some(message) = &self.message {
+            write!(f, ": {}", message)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error {
+    pub(crate) fn from_response(status: StatusCode, body: &str, reset: Option<u64>) -> Error {
+        let error = Box::new(ApiError::from_body(status, body));
+        match status {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Error::Authentication(error),
+            StatusCode::NOT_FOUND => Error::NotFound(error),
+            StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY => {
+                let errors = error
+                    .details
+                    .as_ref()
+                    .and_then(|details| details["errors"].as_array())
+                    .cloned()
+                    .unwrap_or_default();
+                Error::Validation { error, errors }
+            }
+            StatusCode::CONFLICT => Error::VersionConflict(error),
+            StatusCode::TOO_MANY_REQUESTS => Error::RateLimited { error, reset },
+            _ => Error::Api(error),
+        }
+    }
+
+    /// The HTTP status of the response, if the error originated from one.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Error::Authentication(error)
+            | Error::NotFound(error)
+            | Error::Validation { error, .. }
+            | Error::VersionConflict(error)
+            | Error::RateLimited { error, .. }
+            | Error::Api(error) => Some(error.status),
+            Error::Transport(error) => error.status(),
+            Error::Deserialization(_)
+            | Error::InvalidResponse(_)
+            | Error::InvalidInput(_)
+            | Error::AssetNotProcessed { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Transport(error) => write!(f, "transport error: {}", error),
+            Error::Authentication(error) => write!(f, "authentication failed: {}", error),
+            Error::NotFound(error) => write!(f, "not found: {}", error),
+            Error::Validation { error, .. } => write!(f, "validation failed: {}", error),
+            Error::VersionConflict(error) => write!(f, "version conflict: {}", error),
+            Error::RateLimited { error, .. } => write!(f, "rate limited: {}", error),
+            Error::Api(error) => write!(f, "api error: {}", error),
+            Error::Deserialization(error) => write!(f, "deserialization error: {}", error),
+            Error::InvalidResponse(message) => write!(f, "invalid response: {}", message),
+            Error::InvalidInput(message) => write!(f, "invalid input: {}", message),
+            Error::AssetNotProcessed { asset_id, locale } => write!(
+                f,
+                "file of asset {} for locale {} was not processed",
+                asset_id, locale
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Transport(error) => Some(error),
+            Error::Deserialization(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(error: reqwest::Error) -> Self {
+        Error::Transport(error)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(error: serde_json::Error) -> Self {
+        Error::Deserialization(error)
+    }
+}
@@ -1,88 +1,255 @@
-use reqwest::StatusCode;
+use crate::error::{Error, Result};
+use crate::retry_policy::RetryPolicy;
+use reqwest::{header, RequestBuilder, Response, StatusCode};
 use serde::de::DeserializeOwned;
 use serde_json::Value;
+use std::time::Duration;
 
-pub(crate) async fn get<T>(
-    url: &str,
-    bearer_token: &str,
-) -> Result<Option<T>, Box<dyn std::error::Error>>
-where
-    T: DeserializeOwned,
-{
-    let client = reqwest::Client::new();
-    let resp = client.get(url).bearer_auth(bearer_token).send().await?;
+const DEFAULT_USER_AGENT: &str = concat!("contentful-rs/", env!("CARGO_PKG_VERSION"));
 
-    match resp.status() {
-        StatusCode::OK => {
-            let json = resp.json::<T>().await?;
-            Ok(Some(json))
+/// Wraps a single `reqwest::Client` so that all requests of a Contentful client share
+/// its connection pool.
+#[derive(Clone, Debug)]
+pub(crate) struct HttpClient {
+    client: reqwest::Client,
+    user_agent: Option<String>,
+    timeout: Option<Duration>,
+    retry_policy: RetryPolicy,
+}
+
+impl HttpClient {
+    pub(crate) fn new(
+        client: Option<reqwest::Client>,
+        user_agent: Option<String>,
+        timeout: Option<Duration>,
+        retry_policy: RetryPolicy,
+    ) -> HttpClient {
+        // A caller supplied client keeps its own default headers unless a user agent is given
+        let user_agent = match client {
+            Some(_) => user_agent,
+            None => user_agent.or_else(|| Some(DEFAULT_USER_AGENT.into())),
+        };
+        HttpClient {
+            client: client.unwrap_or_default(),
+            user_agent,
+            timeout,
+            retry_policy,
         }
-        StatusCode::NOT_FOUND => Ok(None),
-        _ => {
-            log::warn!("{:?}", &resp);
-            log::warn!("{:?}", &resp.text().await?);
-            todo!("handle response status not < 300");
+    }
+
+    pub(crate) async fn get<T>(&self, url: &str, bearer_token: &str) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        let resp = self
+            .send(self.client.get(url).bearer_auth(bearer_token))
+            .await?;
+
+        read_json::<T>(resp).await
+    }
+
+    /// Gets a single resource, which is `None` if it does not exist.
+    pub(crate) async fn get_optional<T>(&self, url: &str, bearer_token: &str) -> Result<Option<T>>
+    where
+        T: DeserializeOwned,
+    {
+        let resp = self
+            .send(self.client.get(url).bearer_auth(bearer_token))
+            .await?;
+
+        match resp.status() {
+            StatusCode::NOT_FOUND => Ok(None),
+            _ => {
+                let json = read_json::<T>(resp).await?;
+                Ok(Some(json))
+            }
         }
     }
-}
 
-pub(crate) async fn post(
-    url: &str,
-    bearer_token: &str,
-    content_type_id: &str,
-    data: &Value,
-) -> Result<Value, Box<dyn std::error::Error>> {
-    let client = reqwest::Client::new();
-    let resp = client
-        .post(url)
-        .bearer_auth(bearer_token)
-        .header("X-Contentful-Content-Type", content_type_id)
-        .json(&data)
-        .send()
-        .await?;
-
-    match resp.status() {
-        StatusCode::OK | StatusCode::CREATED => {
-            let json = resp.json::<Value>().await?;
-            Ok(json)
+    /// Posts `data`, with the content type for entries, which is not sent for other resources.
+    pub(crate) async fn post(
+        &self,
+        url: &str,
+        bearer_token: &str,
+        content_type_id: Option<&str>,
+        data: &Value,
+    ) -> Result<Value> {
+        let mut builder = self.client.post(url).bearer_auth(bearer_token).json(&data);
+
+        if let Some(content_type_id) = content_type_id {
+            builder = builder.header("X-Contentful-Content-Type", content_type_id);
+        }
+
+        let resp = self.send(builder).await?;
+
+        read_json::<Value>(resp).await
+    }
+
+    /// Posts binary data, e.g. a file to the Upload API.
+    pub(crate) async fn post_bytes(
+        &self,
+        url: &str,
+        bearer_token: &str,
+        data: Vec<u8>,
+    ) -> Result<Value> {
+        let builder = self
+            .client
+            .post(url)
+            .bearer_auth(bearer_token)
+            .header(header::CONTENT_TYPE, "application/octet-stream")
+            .body(data);
+
+        let resp = self.send(builder).await?;
+
+        read_json::<Value>(resp).await
+    }
+
+    pub(crate) async fn put(
+        &self,
+        url: &str,
+        bearer_token: &str,
+        version: &Option<i32>,
+        content_type_id: Option<&str>,
+        data: &Value,
+    ) -> Result<Value> {
+        let mut builder = self.client.put(url).bearer_auth(bearer_token).json(&data);
+
+        if let Some(content_type_id) = content_type_id {
+            builder = builder.header("X-Contentful-Content-Type", content_type_id);
+        }
+
+        if let Some(version) = version {
+            builder = builder.header("X-Contentful-Version", *version);
+        }
+
+        let resp = self.send(builder).await?;
+
+        read_json::<Value>(resp).await
+    }
+
+    /// Puts without a body, e.g. to publish or archive, which requires the current version.
+    /// Returns the response body if there is one, e.g. the published entry.
+    pub(crate) async fn put_version(
+        &self,
+        url: &str,
+        bearer_token: &str,
+        version: i32,
+    ) -> Result<Option<Value>> {
+        let builder = self
+            .client
+            .put(url)
+            .bearer_auth(bearer_token)
+            .header("X-Contentful-Version", version);
+
+        let resp = self.send(builder).await?;
+
+        read_optional_json(resp).await
+    }
+
+    /// Deletes, returning the response body if there is one, e.g. the unpublished entry.
+    pub(crate) async fn delete(
+        &self,
+        url: &str,
+        bearer_token: &str,
+        version: Option<i32>,
+    ) -> Result<Option<Value>> {
+        let mut builder = self.client.delete(url).bearer_auth(bearer_token);
+
+        if let Some(version) = version {
+            builder = builder.header("X-Contentful-Version", version);
+        }
+
+        let resp = self.send(builder).await?;
+
+        read_optional_json(resp).await
+    }
+
+    /// Sends the request, retrying rate limited and failed requests as per the retry policy.
+    async fn send(&self, builder: RequestBuilder) -> Result<Response> {
+        let mut builder = builder;
+        if let Some(user_agent) = &self.user_agent {
+            builder = builder.header(header::USER_AGENT, user_agent);
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
         }
-        _ => {
-            log::warn!("{:?}", &resp);
-            log::warn!("{:?}", &resp.text().await?);
-            todo!("handle response status not < 300");
+        let mut request = builder.build()?;
+        let mut retry = 0;
+        loop {
+            let next_request = request.try_clone();
+            let resp = self.client.execute(request).await?;
+            let status = resp.status();
+
+            match next_request {
+                Some(next_request)
+                    if retry < self.retry_policy.max_retries
+                        && self
+                            .retry_policy
+                            .should_retry(next_request.method(), status) =>
+                {
+                    let delay = self.retry_policy.delay_for(retry, rate_limit_reset(&resp));
+                    log::warn!(
+                        "{} from {}, retrying in {:?} ({}/{})",
+                        status,
+                        resp.url(),
+                        delay,
+                        retry + 1,
+                        self.retry_policy.max_retries
+                    );
+                    tokio::time::sleep(delay).await;
+                    request = next_request;
+                    retry += 1;
+                }
+                _ => return Ok(resp),
+            }
         }
     }
 }
 
-pub(crate) async fn put(
-    url: &str,
-    bearer_token: &str,
-    version: &Option<i32>,
-    content_type_id: &str,
-    data: &Value,
-) -> Result<Value, Box<dyn std::error::Error>> {
-    let client = reqwest::Client::new();
-    let mut builder = client
-        .put(url)
-        .bearer_auth(bearer_token)
-        .header("X-Contentful-Content-Type", content_type_id)
-        .json(&data);
-
-    if let Some(version) = version {
-        builder = builder.header("X-Contentful-Version", *version);
-    }
+fn rate_limit_reset(resp: &Response) -> Option<u64> {
+    resp.headers()
+        .get("X-Contentful-RateLimit-Reset")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok())
+}
 
-    let resp = builder.send().await?;
+async fn read_json<T>(resp: Response) -> Result<T>
+where
+    T: DeserializeOwned,
+{
+    let status = resp.status();
+    if status.is_success() {
+        let body = resp.bytes().await?;
+        let json = serde_json::from_slice::<T>(&body)?;
+        Ok(json)
+    } else {
+        Err(error_from_response(resp).await)
+    }
+}
 
-    match resp.status() {
-        StatusCode::OK | StatusCode::CREATED => {
-            let json = resp.json::<Value>().await?;
-            Ok(json)
+/// Reads the JSON body of a response that may have none, e.g. a 204.
+async fn read_optional_json(resp: Response) -> Result<Option<Value>> {
+    if resp.status().is_success() {
+        let body = resp.bytes().await?;
+        if body.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(serde_json::from_slice::<Value>(&body)?))
         }
-        _ => {
-            log::warn!("{:?}", &resp);
-            log::warn!("{:?}", &resp.text().await?);
-            todo!("handle response status not < 300");
+    } else {
+        Err(error_from_response(resp).await)
+    }
+}
+
+async fn error_from_response(resp: Response) -> Error {
+    let status = resp.status();
+    let reset = rate_limit_reset(&resp);
+    log::warn!("{:?}", &resp);
+    match resp.text().await {
+        Ok(body) => {
+            log::warn!("{:?}", &body);
+            Error::from_response(status, &body, reset)
         }
+        Err(error) => Error::Transport(error),
     }
 }
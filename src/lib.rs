@@ -43,11 +43,18 @@
 
 mod contentful_client;
 mod contentful_management_client;
+mod error;
 mod http_client;
+mod link_resolver;
 pub mod models;
 mod query_builder;
+pub mod renderers;
+mod retry_policy;
 
 pub use crate::{
-    contentful_client::ContentfulClient, contentful_management_client::ContentfulManagementClient,
+    contentful_client::{ContentfulClient, ContentfulClientBuilder},
+    contentful_management_client::{ContentfulManagementClient, ContentfulManagementClientBuilder},
+    error::{ApiError, Error, Result},
     query_builder::QueryBuilder,
+    retry_policy::RetryPolicy,
 };
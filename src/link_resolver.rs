@@ -0,0 +1,342 @@
+use crate::error::{Error, Result};
+use crate::models::LinkReference;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// The `include` depth of the delivery API when a query does not set `include`.
+pub(crate) const DEFAULT_INCLUDE_DEPTH: u32 = 1;
+
+/// Resolves the links of the `items` of a delivery API response in place, returning the links
+/// that could not be resolved.
+pub(crate) fn resolve_response(
+    json: &mut Value,
+    remove_unresolved: bool,
+    max_depth: u32,
+) -> Result<Vec<LinkReference>> {
+    let mut items = match json.get_mut("items") {
+        Some(items) if items.is_array() => items.take(),
+        Some(_) => return Err(Error::InvalidResponse("items is not an array".into())),
+        None => return Err(Error::InvalidResponse("items do not exist".into())),
+    };
+    let mut resolver = LinkResolver::new(&json["includes"], remove_unresolved, max_depth);
+    resolver.resolve_items(&mut items)?;
+    let unresolved_links = resolver.unresolved_links();
+    json["items"] = items;
+    Ok(unresolved_links)
+}
+
+/// Replaces the links of entries with the linked entries and assets from the `includes` of a
+/// delivery API response, i.e. with their fields and `sys`, see `models::Link`. Like the
+/// linked entries, the items are replaced with their fields and `sys`.
+///
+/// Links are resolved up to `max_depth` levels, like the `include` parameter. Links beyond it,
+/// and links back to an entry that is being resolved, are kept as links so that entries that
+/// link to each other do not recurse endlessly.
+pub(crate) struct LinkResolver<'a> {
+    /// The included entries and assets by type and id.
+    includes: HashMap<&'a str, HashMap<&'a str, &'a Value>>,
+    remove_unresolved: bool,
+    max_depth: u32,
+    /// The ids of the entries being resolved, from the item down to the current entry.
+    ancestors: Vec<Value>,
+    unresolved_links: Vec<LinkReference>,
+    /// The resolved linked entries by id and depth, as entries are often linked from many items.
+    resolved: HashMap<(&'a str, u32), Resolved<'a>>,
+    /// The ids of the linked entries resolved so far, to know which a resolved entry contains.
+    entry_ids: Vec<&'a str>,
+    /// The number of links kept because they link back to an entry being resolved, which
+    /// depends on the path to an entry, so that entries resolved with such links are not cached.
+    circular_links: usize,
+}
+
+struct Resolved<'a> {
+    value: Value,
+    /// The ids of the entry and the entries resolved within it.
+    entry_ids: Vec<&'a str>,
+}
+
+impl<'a> LinkResolver<'a> {
+    pub(crate) fn new(includes: &'a Value, remove_unresolved: bool, max_depth: u32) -> Self {
+        let includes = ["Entry", "Asset"]
+            .iter()
+            .map(|&include_type| {
+                let included = includes[include_type]
+                    .as_array()
+                    .map(|included| {
+                        included
+                            .iter()
+                            .filter_map(|item| Some((item["sys"]["id"].as_str()?, item)))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                (include_type, included)
+            })
+            .collect();
+        LinkResolver {
+            includes,
+            remove_unresolved,
+            max_depth,
+            ancestors: Vec::new(),
+            unresolved_links: Vec::new(),
+            resolved: HashMap::new(),
+            entry_ids: Vec::new(),
+            circular_links: 0,
+        }
+    }
+
+    /// The links that were not found in the includes, without duplicates.
+    pub(crate) fn unresolved_links(self) -> Vec<LinkReference> {
+        self.unresolved_links
+    }
+
+    /// Resolves the links of the `items` of a response.
+    pub(crate) fn resolve_items(&mut self, items: &mut Value) -> Result<()> {
+        self.resolve_array(items, 0)
+    }
+
+    fn resolve_array(&mut self, value: &mut Value, depth: u32) -> Result<()> {
+        let items = value.as_array_mut().unwrap();
+        for item in items.iter_mut() {
+            if item.is_object() {
+                self.resolve_object(item, depth)?;
+            } else if item.is_string() || item.is_number() {
+                // do nothing
+            } else {
+                log::error!("Unimplemented item {}", &item);
+                return Err(Error::InvalidResponse(format!(
+                    "Unexpected array item {}",
+                    &item
+                )));
+            }
+        }
+        if self.remove_unresolved {
+            // Unresolved links are replaced with null by resolve_link
+            items.retain(|item| !item.is_null());
+        }
+        Ok(())
+    }
+
+    fn resolve_object(&mut self, value: &mut Value, depth: u32) -> Result<()> {
+        if value["nodeType"] == "document" {
+            // Rich text, deserialized with models::rich_text::Document
+            self.resolve_rich_text(value, depth)?;
+        } else if let Some(sys_type) = value.get("sys").and_then(|sys| sys.get("type")) {
+            if sys_type == "Entry" {
+                self.resolve_entry(value, depth)?;
+            } else if sys_type == "Link" {
+                self.resolve_link(value, depth)?;
+            } else if sys_type == "ResourceLink" {
+                // Entries of other spaces are not included
+                self.add_unresolved_link(value);
+            } else {
+                // Do nothing, as it likely a json object
+            }
+        } else {
+            // Do nothing, as it likely a json object
+        }
+
+        Ok(())
+    }
+
+    fn resolve_entry(&mut self, value: &mut Value, depth: u32) -> Result<()> {
+        let mut fields = match value.get_mut("fields") {
+            Some(fields) => fields.take(),
+            // Entries without any field values, e.g. new drafts in preview, come without fields
+            None => json!({}),
+        };
+        self.ancestors.push(value["sys"]["id"].clone());
+        let resolved = self.resolve_fields(&mut fields, depth);
+        self.ancestors.pop();
+        resolved?;
+        add_sys_and_metadata(&mut fields, value);
+        *value = fields;
+        Ok(())
+    }
+
+    fn resolve_fields(&mut self, fields: &mut Value, depth: u32) -> Result<()> {
+        let entry_object = fields
+            .as_object_mut()
+            .ok_or_else(|| Error::InvalidResponse("fields is not an object".into()))?;
+        for (_field_name, field_value) in entry_object {
+            if field_value.is_object() {
+                self.resolve_object(field_value, depth)?;
+            } else if field_value.is_array() {
+                self.resolve_array(field_value, depth)?;
+            } else {
+                // Regular string, number, etc, values. No need to do anything.
+            }
+        }
+        Ok(())
+    }
+
+    /// Resolves the entries and assets that embedded nodes and entry and asset hyperlinks of a rich
+    /// text node link to.
+    fn resolve_rich_text(&mut self, node: &mut Value, depth: u32) -> Result<()> {
+        if let Some(target) = node.get_mut("data").and_then(|data| data.get_mut("target")) {
+            if target["sys"]["type"] == "Link" {
+                self.resolve_link(target, depth)?;
+            } else if target["sys"]["type"] == "ResourceLink" {
+                self.add_unresolved_link(target);
+            }
+        }
+
+        if let Some(content) = node
+            .get_mut("content")
+            .and_then(|content| content.as_array_mut())
+        {
+            for child in content.iter_mut() {
+                self.resolve_rich_text(child, depth)?;
+            }
+            if self.remove_unresolved {
+                // Unresolved links are replaced with null by resolve_link
+                content.retain(|child| {
+                    child.get("data").and_then(|data| data.get("target")) != Some(&Value::Null)
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn resolve_link(&mut self, value: &mut Value, depth: u32) -> Result<()> {
+        let link_type = value["sys"]["linkType"].clone();
+        let link_id = value["sys"]["id"].clone();
+
+        if depth >= self.max_depth {
+            log::debug!(
+                "Not resolving {} link {} beyond depth {}",
+                &link_type,
+                &link_id,
+                depth
+            );
+            return Ok(());
+        }
+
+        let resolved = if link_type == "Entry" {
+            if self.ancestors.contains(&link_id) {
+                log::debug!("Not resolving circular {} link {}", &link_type, &link_id);
+                self.circular_links += 1;
+                return Ok(());
+            }
+            if let Some(entry) = self.find_included(value) {
+                *value = self.resolve_linked_entry(entry, link_id.clone(), depth)?;
+                true
+            } else {
+                false
+            }
+        } else if link_type == "Asset" {
+            if let Some(asset) = self.find_included(value) {
+                let mut fields = included_fields(asset);
+                add_sys_and_metadata(&mut fields, asset);
+                *value = fields;
+                true
+            } else {
+                false
+            }
+        } else {
+            // Links to anything else can not be resolved from the includes, so they are kept
+            log::debug!("Unexpected {} link {}", &link_type, &link_id);
+            self.add_unresolved_link(value);
+            return Ok(());
+        };
+
+        if !resolved {
+            // Links to unpublished, archived or deleted entries and assets are not included
+            log::debug!("Unresolved {} link {}", &link_type, &link_id);
+            self.add_unresolved_link(value);
+            if self.remove_unresolved {
+                *value = Value::Null;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Adds a link to the unresolved links, with the URN as id for resource links.
+    fn add_unresolved_link(&mut self, link: &Value) {
+        let sys = &link["sys"];
+        let id = sys["id"].as_str().or_else(|| sys["urn"].as_str());
+        if let (Some(link_type), Some(id)) = (sys["linkType"].as_str(), id) {
+            let link = LinkReference::new(id, link_type);
+            if !self.unresolved_links.contains(&link) {
+                self.unresolved_links.push(link);
+            }
+        }
+    }
+
+    /// Resolves an entry linked at `depth`, or returns it as resolved before at the same depth.
+    fn resolve_linked_entry(
+        &mut self,
+        entry: &'a Value,
+        link_id: Value,
+        depth: u32,
+    ) -> Result<Value> {
+        // Included entries are indexed by id, so they have one
+        let id = entry["sys"]["id"].as_str().unwrap_or_default();
+        if let Some(cached) = self.resolved.get(&(id, depth)) {
+            // Links back to an entry being resolved must be kept as links
+            let ancestors = &self.ancestors;
+            if !cached
+                .entry_ids
+                .iter()
+                .any(|entry_id| ancestors.iter().any(|ancestor| ancestor == *entry_id))
+            {
+                let value = cached.value.clone();
+                self.entry_ids.extend_from_slice(&cached.entry_ids);
+                return Ok(value);
+            }
+        }
+
+        let circular_links = self.circular_links;
+        let start = self.entry_ids.len();
+        self.entry_ids.push(id);
+        let mut fields = included_fields(entry);
+        self.ancestors.push(link_id);
+        let resolved = self.resolve_fields(&mut fields, depth + 1);
+        self.ancestors.pop();
+        resolved?;
+        add_sys_and_metadata(&mut fields, entry);
+        if self.circular_links == circular_links {
+            let resolved = Resolved {
+                value: fields.clone(),
+                entry_ids: self.entry_ids[start..].to_vec(),
+            };
+            self.resolved.insert((id, depth), resolved);
+        }
+        Ok(fields)
+    }
+
+    /// Returns the entry or asset a link points to.
+    fn find_included(&self, link: &Value) -> Option<&'a Value> {
+        let link_type = link["sys"]["linkType"].as_str()?;
+        let id = link["sys"]["id"].as_str()?;
+        self.includes.get(link_type)?.get(id).copied()
+    }
+}
+
+/// Clones only the fields of an entry or asset, which replace the entry or link.
+fn included_fields(included: &Value) -> Value {
+    match included.get("fields") {
+        Some(fields) => fields.clone(),
+        // Entries without any field values, e.g. new drafts in preview, come without fields
+        None => json!({}),
+    }
+}
+
+/// Returns the fields of an entry or asset with its `sys` and `metadata` added as fields, which
+/// is how typed entries get them.
+pub(crate) fn fields_with_sys(entry: Value) -> Value {
+    let mut fields = included_fields(&entry);
+    add_sys_and_metadata(&mut fields, &entry);
+    fields
+}
+
+fn add_sys_and_metadata(fields: &mut Value, entry: &Value) {
+    if let Some(fields) = fields.as_object_mut() {
+        for &key in &["sys", "metadata"] {
+            if let Some(value) = entry.get(key) {
+                fields.insert(key.into(), value.clone());
+            }
+        }
+    }
+}
@@ -1,9 +1,15 @@
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use serde::{de, de::DeserializeOwned, ser, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+pub mod rich_text;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Space {
     pub name: String,
+    #[serde(default)]
     pub locales: Vec<Locale>,
     #[serde(rename = "sys")]
     pub system_properties: SystemProperties,
@@ -16,30 +22,72 @@ pub struct Locale {
     pub code: String,
     pub fallback_code: Option<String>,
     pub default: bool,
+    // Only returned by the Content Management API
+    #[serde(default)]
     pub optional: bool,
+    #[serde(default)]
     pub content_management_api: bool,
+    #[serde(default)]
     pub content_delivery_api: bool,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SystemProperties {
     pub id: String,
+    /// e.g. `Entry`, `Asset`, `ContentType` or `Link`.
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub sys_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub space: Option<LinkReference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment: Option<LinkReference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<LinkReference>,
+    /// The locale of the fields, for entries and assets requested with a single locale.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub version: Option<i32>,
+    /// The published revision, which the delivery APIs have instead of versions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub revision: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub created_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub updated_at: Option<DateTime<Utc>>,
-    // TODO: More fields
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<LinkReference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_by: Option<LinkReference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub published_version: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub published_counter: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub published_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_published_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub published_by: Option<LinkReference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archived_version: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archived_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archived_by: Option<LinkReference>,
+    /// When the entry or asset was deleted, for the deletions returned by the Sync API.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 impl SystemProperties {
     pub fn new(id: String) -> SystemProperties {
         SystemProperties {
             id,
-            version: None,
-            revision: None,
-            created_at: None,
-            updated_at: None,
+            ..Default::default()
         }
     }
 
@@ -47,11 +95,50 @@ impl SystemProperties {
         SystemProperties {
             id,
             version: Some(version),
-            revision: None,
-            created_at: None,
-            updated_at: None,
+            ..Default::default()
+        }
+    }
+
+    // The status helpers use the versions returned by the Content Management API. Publishing
+    // increments the version, so a published entry that has not changed since has a version
+    // one more than its published version. The delivery APIs have a revision instead of
+    // versions, and entries of the Content Delivery API are published. Drafts of the Content
+    // Preview API can not be told apart from published entries by their sys.
+
+    /// Whether the entry or asset has never been published, or was unpublished.
+    pub fn is_draft(&self) -> bool {
+        !self.is_archived() && !self.is_delivered() && self.published_version.is_none()
+    }
+
+    /// Whether the entry or asset is published without changes since.
+    pub fn is_published(&self) -> bool {
+        if self.is_archived() {
+            return false;
+        }
+        match (self.version, self.published_version) {
+            (Some(version), Some(published_version)) => version == published_version + 1,
+            _ => self.is_delivered(),
         }
     }
+
+    /// Whether the entry or asset is published and has changed since.
+    pub fn is_changed(&self) -> bool {
+        match (self.version, self.published_version) {
+            (Some(version), Some(published_version)) => {
+                !self.is_archived() && version > published_version + 1
+            }
+            _ => false,
+        }
+    }
+
+    pub fn is_archived(&self) -> bool {
+        self.archived_version.is_some() || self.archived_at.is_some()
+    }
+
+    /// Whether the sys is from a delivery API, which has a revision instead of versions.
+    fn is_delivered(&self) -> bool {
+        self.version.is_none() && self.revision.is_some()
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -59,6 +146,8 @@ impl SystemProperties {
 pub struct Entry<T> {
     pub sys: SystemProperties,
     pub fields: T,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Metadata>,
 }
 
 impl<T> Entry<T>
@@ -66,7 +155,332 @@ where
     T: Serialize,
 {
     pub fn new(entry: T, sys: SystemProperties) -> Entry<T> {
-        Entry { sys, fields: entry }
+        Entry {
+            sys,
+            fields: entry,
+            metadata: None,
+        }
+    }
+}
+
+/// The tags and taxonomy concepts of an entry or asset.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Metadata {
+    #[serde(default)]
+    pub tags: Vec<LinkReference>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub concepts: Vec<LinkReference>,
+}
+
+impl Metadata {
+    pub fn tag_ids(&self) -> impl Iterator<Item = &str> {
+        self.tags.iter().map(|tag| tag.id.as_str())
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Tag {
+    pub name: String,
+    pub sys: SystemProperties,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentType {
+    pub sys: SystemProperties,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The id of the field used as the title of entries.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_field: Option<String>,
+    pub fields: Vec<ContentTypeField>,
+    /// Properties not known to this crate, kept so that they survive a round trip.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl ContentType {
+    /// A content type to create with `ContentfulManagementClient::create_or_update_content_type`.
+    pub fn new(id: &str, name: &str, fields: Vec<ContentTypeField>) -> ContentType {
+        ContentType {
+            sys: SystemProperties::new(id.into()),
+            name: name.into(),
+            description: None,
+            display_field: None,
+            fields,
+            extra: Map::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentTypeField {
+    pub id: String,
+    pub name: String,
+    /// e.g. `Symbol`, `Text`, `RichText`, `Integer`, `Link` or `Array`.
+    #[serde(rename = "type")]
+    pub field_type: String,
+    /// `Entry` or `Asset` for fields of type `Link`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link_type: Option<String>,
+    /// The type of the items of fields of type `Array`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub items: Option<ContentTypeFieldItems>,
+    #[serde(default)]
+    pub localized: bool,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default)]
+    pub validations: Vec<Value>,
+    #[serde(default)]
+    pub disabled: bool,
+    #[serde(default)]
+    pub omitted: bool,
+    /// Set to delete the field, after omitting it, when saving the content type.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub deleted: bool,
+    /// The value of new entries, by locale.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_value: Option<Value>,
+    /// The content types of other spaces that fields of type `ResourceLink` can link to.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_resources: Vec<Value>,
+    /// Properties not known to this crate, kept so that they survive a round trip.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl ContentTypeField {
+    /// A field that is neither localized nor required, without validations.
+    pub fn new(id: &str, name: &str, field_type: &str) -> ContentTypeField {
+        ContentTypeField {
+            id: id.into(),
+            name: name.into(),
+            field_type: field_type.into(),
+            link_type: None,
+            items: None,
+            localized: false,
+            required: false,
+            validations: Vec::new(),
+            disabled: false,
+            omitted: false,
+            deleted: false,
+            default_value: None,
+            allowed_resources: Vec::new(),
+            extra: Map::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentTypeFieldItems {
+    #[serde(rename = "type")]
+    pub item_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link_type: Option<String>,
+    #[serde(default)]
+    pub validations: Vec<Value>,
+    /// Properties not known to this crate, kept so that they survive a round trip.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// A page of a collection, e.g. the entries matching a query, with its totals.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Collection<T> {
+    pub items: Vec<T>,
+    /// The number of items in the whole collection.
+    pub total: u32,
+    pub skip: u32,
+    pub limit: u32,
+    /// The linked entries and assets included in the response, by `Entry` and `Asset`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub includes: Option<Value>,
+    /// Errors of the response, e.g. `notResolvable` for links to entries or assets that are
+    /// not published or were deleted.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<Value>,
+    /// The links of the items that could not be resolved, from `errors` and the includes.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unresolved_links: Vec<LinkReference>,
+}
+
+/// A link to an entry or asset, e.g. a link that could not be resolved.
+///
+/// (De)serializes as a link, i.e. `{"sys": {"type": "Link", "linkType": "Entry", "id": "..."}}`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "LinkJson", into = "LinkJson")]
+pub struct LinkReference {
+    /// The id, or the URN of a resource link to an entry of another space.
+    pub id: String,
+    /// `Entry` or `Asset`, or e.g. `Contentful:Entry` for resource links.
+    pub link_type: String,
+}
+
+impl LinkReference {
+    pub fn new(id: &str, link_type: &str) -> LinkReference {
+        LinkReference {
+            id: id.into(),
+            link_type: link_type.into(),
+        }
+    }
+}
+
+/// A linked entry or asset, e.g. the type of a field of type `Link`, that keeps the `sys` of
+/// resolved entries and assets.
+///
+/// ```
+/// use contentful::models::{Asset, Link};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Product {
+///     title: String,
+///     image: Link<Asset>,
+///     brand: Option<Link<Brand>>,
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct Brand {
+///     name: String,
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub enum Link<T> {
+    /// A link resolved by `ContentfulClient` to the linked entry or asset.
+    Resolved {
+        sys: Box<SystemProperties>,
+        fields: T,
+    },
+    /// A link that was not resolved, e.g. to an entry that is not published or beyond the
+    /// `include` depth.
+    Unresolved { id: String, link_type: String },
+}
+
+impl<T> Link<T> {
+    /// The id of the linked entry or asset.
+    pub fn id(&self) -> &str {
+        match self {
+            Link::Resolved { sys, .. } => &sys.id,
+            Link::Unresolved { id, .. } => id,
+        }
+    }
+
+    pub fn is_resolved(&self) -> bool {
+        matches!(self, Link::Resolved { .. })
+    }
+
+    pub fn sys(&self) -> Option<&SystemProperties> {
+        match self {
+            Link::Resolved { sys, .. } => Some(sys.as_ref()),
+            Link::Unresolved { .. } => None,
+        }
+    }
+
+    pub fn fields(&self) -> Option<&T> {
+        match self {
+            Link::Resolved { fields, .. } => Some(fields),
+            Link::Unresolved { .. } => None,
+        }
+    }
+}
+
+/// Resolved links (de)serialize as the fields of the entry or asset with its `sys` added as a
+/// field, and unresolved links as links.
+impl<T> Serialize for Link<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Link::Resolved { sys, fields } => {
+                let mut value = serde_json::to_value(fields).map_err(ser::Error::custom)?;
+                let sys = serde_json::to_value(sys).map_err(ser::Error::custom)?;
+                match value.as_object_mut() {
+                    Some(object) => object.insert("sys".into(), sys),
+                    None => return Err(ser::Error::custom("link fields are not an object")),
+                };
+                value.serialize(serializer)
+            }
+            Link::Unresolved { id, link_type } => {
+                LinkReference::new(id, link_type).serialize(serializer)
+            }
+        }
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Link<T>
+where
+    T: DeserializeOwned,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut value = Value::deserialize(deserializer)?;
+        if value["sys"]["type"] == "Link" {
+            let link = serde_json::from_value::<LinkReference>(value).map_err(de::Error::custom)?;
+            return Ok(Link::Unresolved {
+                id: link.id,
+                link_type: link.link_type,
+            });
+        }
+
+        let sys = value
+            .as_object_mut()
+            .and_then(|object| object.remove("sys"))
+            .ok_or_else(|| de::Error::missing_field("sys"))?;
+        Ok(Link::Resolved {
+            sys: serde_json::from_value(sys).map_err(de::Error::custom)?,
+            fields: serde_json::from_value(value).map_err(de::Error::custom)?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct LinkJson {
+    sys: LinkSys,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LinkSys {
+    #[serde(rename = "type")]
+    sys_type: String,
+    link_type: String,
+    id: String,
+}
+
+impl TryFrom<LinkJson> for LinkReference {
+    type Error = String;
+
+    fn try_from(link: LinkJson) -> Result<Self, Self::Error> {
+        if link.sys.sys_type == "Link" {
+            Ok(LinkReference {
+                id: link.sys.id,
+                link_type: link.sys.link_type,
+            })
+        } else {
+            Err(format!("expected a Link, found {}", link.sys.sys_type))
+        }
+    }
+}
+
+impl From<LinkReference> for LinkJson {
+    fn from(link: LinkReference) -> Self {
+        LinkJson {
+            sys: LinkSys {
+                sys_type: "Link".into(),
+                link_type: link.link_type,
+                id: link.id,
+            },
+        }
     }
 }
 
@@ -75,6 +489,8 @@ pub struct Asset {
     pub description: Option<String>,
     pub title: Option<String>,
     pub file: File,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sys: Option<SystemProperties>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -98,3 +514,106 @@ pub struct ImageDetails {
     pub height: i32,
     pub width: i32,
 }
+
+/// The fields of an asset of the management API, which have a value per locale. Assets of the
+/// management API are returned as `Entry<AssetFields>`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct AssetFields {
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub title: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub description: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub file: HashMap<String, AssetFile>,
+}
+
+/// The file of an asset for a locale as the management API has it, before and after it is
+/// processed.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetFile {
+    pub file_name: String,
+    pub content_type: String,
+    /// The upload the file is processed from, see `ContentfulManagementClient::upload`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upload_from: Option<LinkReference>,
+    /// A public URL the file is processed from, instead of an upload.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upload: Option<String>,
+    /// The URL of the processed file, which is only set once the file is processed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<FileDetails>,
+}
+
+impl AssetFile {
+    /// A file to be processed from the upload with the given id.
+    pub fn from_upload(file_name: &str, content_type: &str, upload_id: &str) -> AssetFile {
+        AssetFile {
+            file_name: file_name.into(),
+            content_type: content_type.into(),
+            upload_from: Some(LinkReference::new(upload_id, "Upload")),
+            upload: None,
+            url: None,
+            details: None,
+        }
+    }
+
+    pub fn is_processed(&self) -> bool {
+        self.url.is_some()
+    }
+}
+
+/// A file uploaded to the Upload API, which assets are processed from until it expires.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Upload {
+    pub sys: SystemProperties,
+}
+
+/// A page of results of the Sync API, merged with all following pages.
+#[derive(Clone, Debug)]
+pub struct SyncResult {
+    pub items: Vec<SyncItem>,
+    /// Pass this token to `ContentfulClient::sync` to get the changes since this sync.
+    pub next_sync_token: String,
+}
+
+impl SyncResult {
+    pub fn entries(&self) -> impl Iterator<Item = &Entry<Value>> {
+        self.items.iter().filter_map(|item| match item {
+            SyncItem::Entry(entry) => Some(entry),
+            _ => None,
+        })
+    }
+
+    pub fn assets(&self) -> impl Iterator<Item = &Entry<Value>> {
+        self.items.iter().filter_map(|item| match item {
+            SyncItem::Asset(asset) => Some(asset),
+            _ => None,
+        })
+    }
+
+    pub fn deleted_entries(&self) -> impl Iterator<Item = &SystemProperties> {
+        self.items.iter().filter_map(|item| match item {
+            SyncItem::DeletedEntry(sys) => Some(sys),
+            _ => None,
+        })
+    }
+
+    pub fn deleted_assets(&self) -> impl Iterator<Item = &SystemProperties> {
+        self.items.iter().filter_map(|item| match item {
+            SyncItem::DeletedAsset(sys) => Some(sys),
+            _ => None,
+        })
+    }
+}
+
+/// An item returned by the Sync API. Entries and assets have the values of all locales.
+#[derive(Clone, Debug)]
+pub enum SyncItem {
+    Entry(Entry<Value>),
+    Asset(Entry<Value>),
+    DeletedEntry(SystemProperties),
+    DeletedAsset(SystemProperties),
+}
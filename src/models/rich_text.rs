@@ -0,0 +1,336 @@
+//! The document tree of fields of type `RichText`.
+//!
+//! A rich text field deserializes into a [`Document`], e.g.
+//!
+//! ```
+//! use contentful::models::rich_text::{Document, Node};
+//! use serde_json::json;
+//!
+//! let document: Document = serde_json::from_value(json!({
+//!     "nodeType": "document",
+//!     "data": {},
+//!     "content": [{
+//!         "nodeType": "paragraph",
+//!         "data": {},
+//!         "content": [{ "nodeType": "text", "value": "Hello", "marks": [], "data": {} }]
+//!     }]
+//! }))
+//! .unwrap();
+//! assert!(matches!(document.content[0], Node::Paragraph(_)));
+//! ```
+mod markdown;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::convert::TryFrom;
+
+/// The root of a rich text field.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "Node", into = "Node")]
+pub struct Document {
+    pub content: Vec<Node>,
+    pub data: Map<String, Value>,
+}
+
+impl Document {
+    pub fn new(content: Vec<Node>) -> Document {
+        Document {
+            content,
+            data: Map::new(),
+        }
+    }
+}
+
+impl TryFrom<Node> for Document {
+    type Error = String;
+
+    fn try_from(node: Node) -> Result<Self, Self::Error> {
+        match node {
+            Node::Document(block) => Ok(Document {
+                content: block.content,
+                data: block.data,
+            }),
+            other => Err(format!("expected a document, found {}", other.node_type())),
+        }
+    }
+}
+
+impl From<Document> for Node {
+    fn from(document: Document) -> Self {
+        Node::Document(Block {
+            content: document.content,
+            data: document.data,
+        })
+    }
+}
+
+/// A node of a rich text document, tagged by its `nodeType`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "nodeType")]
+pub enum Node {
+    #[serde(rename = "document")]
+    Document(Block),
+    #[serde(rename = "paragraph")]
+    Paragraph(Block),
+    #[serde(rename = "heading-1")]
+    Heading1(Block),
+    #[serde(rename = "heading-2")]
+    Heading2(Block),
+    #[serde(rename = "heading-3")]
+    Heading3(Block),
+    #[serde(rename = "heading-4")]
+    Heading4(Block),
+    #[serde(rename = "heading-5")]
+    Heading5(Block),
+    #[serde(rename = "heading-6")]
+    Heading6(Block),
+    #[serde(rename = "ordered-list")]
+    OrderedList(Block),
+    #[serde(rename = "unordered-list")]
+    UnorderedList(Block),
+    #[serde(rename = "list-item")]
+    ListItem(Block),
+    #[serde(rename = "blockquote")]
+    Blockquote(Block),
+    #[serde(rename = "hr")]
+    Hr(Block),
+    #[serde(rename = "table")]
+    Table(Block),
+    #[serde(rename = "table-row")]
+    TableRow(Block),
+    #[serde(rename = "table-header-cell")]
+    TableHeaderCell(Block),
+    #[serde(rename = "table-cell")]
+    TableCell(Block),
+    #[serde(rename = "embedded-entry-block")]
+    EmbeddedEntryBlock(Embedded),
+    #[serde(rename = "embedded-asset-block")]
+    EmbeddedAssetBlock(Embedded),
+    #[serde(rename = "embedded-entry-inline")]
+    EmbeddedEntryInline(Embedded),
+    #[serde(rename = "hyperlink")]
+    Hyperlink(Hyperlink),
+    #[serde(rename = "entry-hyperlink")]
+    EntryHyperlink(Embedded),
+    #[serde(rename = "asset-hyperlink")]
+    AssetHyperlink(Embedded),
+    /// An entry of another space, linked with a `ResourceLink`.
+    #[serde(rename = "embedded-resource-block")]
+    EmbeddedResourceBlock(Embedded),
+    #[serde(rename = "embedded-resource-inline")]
+    EmbeddedResourceInline(Embedded),
+    #[serde(rename = "resource-hyperlink")]
+    ResourceHyperlink(Embedded),
+    #[serde(rename = "text")]
+    Text(Text),
+    /// A node type not known to this crate, kept so that it survives a round trip.
+    #[serde(untagged)]
+    Unknown(UnknownNode),
+}
+
+impl Node {
+    /// The `nodeType` of the node, e.g. `paragraph` or `heading-1`.
+    pub fn node_type(&self) -> &str {
+        match self {
+            Node::Document(_) => "document",
+            Node::Paragraph(_) => "paragraph",
+            Node::Heading1(_) => "heading-1",
+            Node::Heading2(_) => "heading-2",
+            Node::Heading3(_) => "heading-3",
+            Node::Heading4(_) => "heading-4",
+            Node::Heading5(_) => "heading-5",
+            Node::Heading6(_) => "heading-6",
+            Node::OrderedList(_) => "ordered-list",
+            Node::UnorderedList(_) => "unordered-list",
+            Node::ListItem(_) => "list-item",
+            Node::Blockquote(_) => "blockquote",
+            Node::Hr(_) => "hr",
+            Node::Table(_) => "table",
+            Node::TableRow(_) => "table-row",
+            Node::TableHeaderCell(_) => "table-header-cell",
+            Node::TableCell(_) => "table-cell",
+            Node::EmbeddedEntryBlock(_) => "embedded-entry-block",
+            Node::EmbeddedAssetBlock(_) => "embedded-asset-block",
+            Node::EmbeddedEntryInline(_) => "embedded-entry-inline",
+            Node::Hyperlink(_) => "hyperlink",
+            Node::EntryHyperlink(_) => "entry-hyperlink",
+            Node::AssetHyperlink(_) => "asset-hyperlink",
+            Node::EmbeddedResourceBlock(_) => "embedded-resource-block",
+            Node::EmbeddedResourceInline(_) => "embedded-resource-inline",
+            Node::ResourceHyperlink(_) => "resource-hyperlink",
+            Node::Text(_) => "text",
+            Node::Unknown(unknown) => &unknown.node_type,
+        }
+    }
+
+    /// The child nodes, empty for text nodes.
+    pub fn content(&self) -> &[Node] {
+        match self {
+            Node::Document(block)
+            | Node::Paragraph(block)
+            | Node::Heading1(block)
+            | Node::Heading2(block)
+            | Node::Heading3(block)
+            | Node::Heading4(block)
+            | Node::Heading5(block)
+            | Node::Heading6(block)
+            | Node::OrderedList(block)
+            | Node::UnorderedList(block)
+            | Node::ListItem(block)
+            | Node::Blockquote(block)
+            | Node::Hr(block)
+            | Node::Table(block)
+            | Node::TableRow(block)
+            | Node::TableHeaderCell(block)
+            | Node::TableCell(block) => &block.content,
+            Node::EmbeddedEntryBlock(embedded)
+            | Node::EmbeddedAssetBlock(embedded)
+            | Node::EmbeddedEntryInline(embedded)
+            | Node::EntryHyperlink(embedded)
+            | Node::AssetHyperlink(embedded)
+            | Node::EmbeddedResourceBlock(embedded)
+            | Node::EmbeddedResourceInline(embedded)
+            | Node::ResourceHyperlink(embedded) => &embedded.content,
+            Node::Hyperlink(hyperlink) => &hyperlink.content,
+            Node::Text(_) => &[],
+            Node::Unknown(unknown) => &unknown.content,
+        }
+    }
+}
+
+/// A node without specific data, e.g. a paragraph or a list.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Block {
+    #[serde(default)]
+    pub content: Vec<Node>,
+    #[serde(default)]
+    pub data: Map<String, Value>,
+}
+
+impl Block {
+    pub fn new(content: Vec<Node>) -> Block {
+        Block {
+            content,
+            data: Map::new(),
+        }
+    }
+}
+
+/// A link to a web page.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Hyperlink {
+    #[serde(default)]
+    pub content: Vec<Node>,
+    pub data: HyperlinkData,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HyperlinkData {
+    pub uri: String,
+}
+
+/// An embedded entry or asset, or a hyperlink to one.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Embedded {
+    #[serde(default)]
+    pub content: Vec<Node>,
+    pub data: EmbeddedData,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EmbeddedData {
+    /// The link to the entry or asset, e.g. `{"sys": {"type": "Link", "linkType": "Entry", "id": "..."}}`.
+    /// `ContentfulClient` replaces included entries and assets with their fields and `sys`.
+    pub target: Value,
+}
+
+/// A node of a type not known to this crate.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct UnknownNode {
+    #[serde(rename = "nodeType")]
+    pub node_type: String,
+    #[serde(default)]
+    pub content: Vec<Node>,
+    #[serde(default)]
+    pub data: Map<String, Value>,
+}
+
+/// A run of text with its formatting.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Text {
+    pub value: String,
+    #[serde(default)]
+    pub marks: Vec<Mark>,
+    #[serde(default)]
+    pub data: Map<String, Value>,
+}
+
+impl Text {
+    pub fn new(value: &str) -> Text {
+        Text {
+            value: value.into(),
+            marks: Vec::new(),
+            data: Map::new(),
+        }
+    }
+
+    pub fn has_mark(&self, mark_type: &MarkType) -> bool {
+        self.marks.iter().any(|mark| &mark.mark_type == mark_type)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Mark {
+    #[serde(rename = "type")]
+    pub mark_type: MarkType,
+}
+
+impl Mark {
+    pub fn new(mark_type: MarkType) -> Mark {
+        Mark { mark_type }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum MarkType {
+    Bold,
+    Italic,
+    Underline,
+    Code,
+    Superscript,
+    Subscript,
+    Strikethrough,
+    /// A mark not known to this crate, kept so that it survives a round trip.
+    Other(String),
+}
+
+impl From<String> for MarkType {
+    fn from(mark_type: String) -> Self {
+        match mark_type.as_str() {
+            "bold" => MarkType::Bold,
+            "italic" => MarkType::Italic,
+            "underline" => MarkType::Underline,
+            "code" => MarkType::Code,
+            "superscript" => MarkType::Superscript,
+            "subscript" => MarkType::Subscript,
+            "strikethrough" => MarkType::Strikethrough,
+            _ => MarkType::Other(mark_type),
+        }
+    }
+}
+
+impl From<MarkType> for String {
+    fn from(mark_type: MarkType) -> Self {
+        match mark_type {
+            MarkType::Bold => "bold".into(),
+            MarkType::Italic => "italic".into(),
+            MarkType::Underline => "underline".into(),
+            MarkType::Code => "code".into(),
+            MarkType::Superscript => "superscript".into(),
+            MarkType::Subscript => "subscript".into(),
+            MarkType::Strikethrough => "strikethrough".into(),
+            MarkType::Other(mark_type) => mark_type,
+        }
+    }
+}
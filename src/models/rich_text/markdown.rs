@@ -0,0 +1,293 @@
+use crate::models::rich_text::{
+    Block, Document, Hyperlink, HyperlinkData, Mark, MarkType, Node, Text,
+};
+use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag};
+
+impl Document {
+    /// Parses CommonMark, with tables and strikethrough, into a rich text document, e.g. to
+    /// create entries with `ContentfulManagementClient`.
+    ///
+    /// Code blocks become paragraphs with the `code` mark, and images, which rich text can only
+    /// embed as assets, become hyperlinks, or their text inside links. Quotes only keep the
+    /// paragraphs of their content, and empty quotes, lists and list items are left out, as
+    /// the Content Management API rejects them.
+    pub fn from_markdown(markdown: &str) -> Document {
+        let options = Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH;
+        let mut parser = MarkdownParser {
+            frames: vec![Frame::new(Kind::Document)],
+            marks: Vec::new(),
+            images: Vec::new(),
+        };
+        for event in Parser::new_ext(markdown, options) {
+            parser.parse(event);
+        }
+        while parser.frames.len() > 1 {
+            parser.end();
+        }
+        Document::new(parser.frames.pop().unwrap().content)
+    }
+}
+
+/// A node being built while parsing, with the content parsed so far.
+struct Frame {
+    kind: Kind,
+    content: Vec<Node>,
+}
+
+impl Frame {
+    fn new(kind: Kind) -> Frame {
+        Frame {
+            kind,
+            content: Vec::new(),
+        }
+    }
+}
+
+enum Kind {
+    Document,
+    Paragraph,
+    /// A paragraph around text directly inside a list item, quote or table cell, which rich
+    /// text requires but CommonMark leaves out for tight lists and tables.
+    ImplicitParagraph,
+    Heading(HeadingLevel),
+    Blockquote,
+    OrderedList,
+    UnorderedList,
+    ListItem,
+    Table,
+    TableRow {
+        header: bool,
+    },
+    TableCell {
+        header: bool,
+    },
+    Hyperlink(String),
+}
+
+struct MarkdownParser {
+    frames: Vec<Frame>,
+    marks: Vec<MarkType>,
+    /// Whether each open image started a hyperlink, which it does not inside a link as rich
+    /// text does not allow nested hyperlinks.
+    images: Vec<bool>,
+}
+
+impl MarkdownParser {
+    fn parse(&mut self, event: Event) {
+        match event {
+            Event::Start(tag) => match tag {
+                Tag::Paragraph => self.start(Kind::Paragraph),
+                Tag::Heading(level, _, _) => self.start(Kind::Heading(level)),
+                Tag::BlockQuote => self.start(Kind::Blockquote),
+                Tag::CodeBlock(_) => {
+                    self.start(Kind::Paragraph);
+                    self.marks.push(MarkType::Code);
+                }
+                Tag::List(Some(_)) => self.start(Kind::OrderedList),
+                Tag::List(None) => self.start(Kind::UnorderedList),
+                Tag::Item => self.start(Kind::ListItem),
+                Tag::Table(_) => self.start(Kind::Table),
+                Tag::TableHead => self.start(Kind::TableRow { header: true }),
+                Tag::TableRow => self.start(Kind::TableRow { header: false }),
+                Tag::TableCell => {
+                    let header = matches!(
+                        self.frames.last().map(|frame| &frame.kind),
+                        Some(Kind::TableRow { header: true })
+                    );
+                    self.start(Kind::TableCell { header })
+                }
+                Tag::Emphasis => self.marks.push(MarkType::Italic),
+                Tag::Strong => self.marks.push(MarkType::Bold),
+                Tag::Strikethrough => self.marks.push(MarkType::Strikethrough),
+                Tag::Link(_, url, _) => self.start_inline(Kind::Hyperlink(url.to_string())),
+                Tag::Image(_, url, _) => {
+                    let in_hyperlink = self
+                        .frames
+                        .iter()
+                        .any(|frame| matches!(frame.kind, Kind::Hyperlink(_)));
+                    if !in_hyperlink {
+                        self.start_inline(Kind::Hyperlink(url.to_string()));
+                    }
+                    self.images.push(!in_hyperlink);
+                }
+                Tag::FootnoteDefinition(_) => self.start(Kind::Paragraph),
+            },
+            Event::End(tag) => match tag {
+                Tag::CodeBlock(_) => {
+                    self.marks.pop();
+                    // The code of code blocks ends with a new line
+                    self.trim_end_of_text();
+                    self.end();
+                }
+                Tag::Emphasis | Tag::Strong | Tag::Strikethrough => {
+                    self.marks.pop();
+                }
+                Tag::Link(..) => self.end(),
+                Tag::Image(..) => {
+                    if self.images.pop() == Some(true) {
+                        self.end();
+                    }
+                }
+                _ => {
+                    self.end_implicit_paragraph();
+                    self.end();
+                }
+            },
+            Event::Text(text) | Event::Html(text) => self.text(&text),
+            Event::Code(code) => {
+                self.marks.push(MarkType::Code);
+                self.text(&code);
+                self.marks.pop();
+            }
+            Event::SoftBreak => self.text(" "),
+            Event::HardBreak => self.text("\n"),
+            Event::Rule => {
+                self.end_implicit_paragraph();
+                self.push(Node::Hr(Block::default()));
+            }
+            Event::FootnoteReference(_) | Event::TaskListMarker(_) => {}
+        }
+    }
+
+    fn start(&mut self, kind: Kind) {
+        self.end_implicit_paragraph();
+        self.frames.push(Frame::new(kind));
+    }
+
+    fn start_inline(&mut self, kind: Kind) {
+        self.start_implicit_paragraph();
+        self.frames.push(Frame::new(kind));
+    }
+
+    fn end(&mut self) {
+        let mut frame = self.frames.pop().unwrap();
+        if let Kind::Blockquote = frame.kind {
+            frame.content = paragraphs(frame.content);
+        }
+        if frame.content.is_empty() {
+            match frame.kind {
+                // Rich text requires a paragraph in table cells, also in empty ones
+                Kind::TableCell { .. } => frame
+                    .content
+                    .push(Node::Paragraph(Block::new(vec![Node::Text(Text::new(""))]))),
+                Kind::Paragraph
+                | Kind::ImplicitParagraph
+                | Kind::Heading(_)
+                | Kind::Hyperlink(_) => frame.content.push(Node::Text(Text::new(""))),
+                // Rich text does not allow empty quotes, lists and tables
+                Kind::Blockquote
+                | Kind::OrderedList
+                | Kind::UnorderedList
+                | Kind::ListItem
+                | Kind::Table
+                | Kind::TableRow { .. } => return,
+                Kind::Document => {}
+            }
+        }
+        let block = Block::new(frame.content);
+        let node = match frame.kind {
+            Kind::Document => Node::Document(block),
+            Kind::Paragraph | Kind::ImplicitParagraph => Node::Paragraph(block),
+            Kind::Heading(HeadingLevel::H1) => Node::Heading1(block),
+            Kind::Heading(HeadingLevel::H2) => Node::Heading2(block),
+            Kind::Heading(HeadingLevel::H3) => Node::Heading3(block),
+            Kind::Heading(HeadingLevel::H4) => Node::Heading4(block),
+            Kind::Heading(HeadingLevel::H5) => Node::Heading5(block),
+            Kind::Heading(HeadingLevel::H6) => Node::Heading6(block),
+            Kind::Blockquote => Node::Blockquote(block),
+            Kind::OrderedList => Node::OrderedList(block),
+            Kind::UnorderedList => Node::UnorderedList(block),
+            Kind::ListItem => Node::ListItem(block),
+            Kind::Table => Node::Table(block),
+            Kind::TableRow { .. } => Node::TableRow(block),
+            Kind::TableCell { header: true } => Node::TableHeaderCell(block),
+            Kind::TableCell { header: false } => Node::TableCell(block),
+            Kind::Hyperlink(uri) => Node::Hyperlink(Hyperlink {
+                content: block.content,
+                data: HyperlinkData { uri },
+            }),
+        };
+        self.push(node);
+    }
+
+    fn start_implicit_paragraph(&mut self) {
+        if let Some(Kind::Document)
+        | Some(Kind::ListItem)
+        | Some(Kind::Blockquote)
+        | Some(Kind::TableCell { .. }) = self.frames.last().map(|frame| &frame.kind)
+        {
+            self.frames.push(Frame::new(Kind::ImplicitParagraph));
+        }
+    }
+
+    fn end_implicit_paragraph(&mut self) {
+        if let Some(Kind::ImplicitParagraph) = self.frames.last().map(|frame| &frame.kind) {
+            self.end();
+        }
+    }
+
+    fn push(&mut self, node: Node) {
+        if let Some(frame) = self.frames.last_mut() {
+            frame.content.push(node);
+        }
+    }
+
+    fn text(&mut self, value: &str) {
+        self.start_implicit_paragraph();
+        let marks = self
+            .marks
+            .iter()
+            .map(|mark_type| Mark::new(mark_type.clone()))
+            .collect::<Vec<_>>();
+        let frame = self.frames.last_mut().unwrap();
+        // The parser splits text, e.g. at escaped characters, so text with the same marks is joined
+        if let Some(Node::Text(text)) = frame.content.last_mut() {
+            if text.marks == marks {
+                text.value.push_str(value);
+                return;
+            }
+        }
+        let mut text = Text::new(value);
+        text.marks = marks;
+        frame.content.push(Node::Text(text));
+    }
+
+    fn trim_end_of_text(&mut self) {
+        if let Some(Node::Text(text)) = self
+            .frames
+            .last_mut()
+            .and_then(|frame| frame.content.last_mut())
+        {
+            let len = text.value.trim_end_matches('\n').len();
+            text.value.truncate(len);
+        }
+    }
+}
+
+/// The content of a quote as paragraphs, which are all rich text allows in quotes. Headings
+/// become paragraphs, and the paragraphs of lists, quotes and tables are taken out of them.
+fn paragraphs(nodes: Vec<Node>) -> Vec<Node> {
+    let mut content = Vec::new();
+    for node in nodes {
+        match node {
+            Node::Paragraph(block)
+            | Node::Heading1(block)
+            | Node::Heading2(block)
+            | Node::Heading3(block)
+            | Node::Heading4(block)
+            | Node::Heading5(block)
+            | Node::Heading6(block) => content.push(Node::Paragraph(block)),
+            Node::Hr(_) => {}
+            Node::Blockquote(block)
+            | Node::OrderedList(block)
+            | Node::UnorderedList(block)
+            | Node::ListItem(block)
+            | Node::Table(block)
+            | Node::TableRow(block)
+            | Node::TableHeaderCell(block)
+            | Node::TableCell(block) => content.extend(paragraphs(block.content)),
+            other => content.push(other),
+        }
+    }
+    content
+}
@@ -1,12 +1,28 @@
-use std::collections::HashMap;
-
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+
+/// Characters left as is in query keys and values, besides alphanumerics.
+const QUERY_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~')
+    .remove(b',')
+    .remove(b'[')
+    .remove(b']');
+
+/// Builds the query string of a Contentful request.
+///
+/// Parameters are kept in the order they are added. Setting a parameter that already
+/// exists replaces its value, except with [`QueryBuilder::add_query_param`], which allows
+/// repeated keys.
+#[derive(Clone, Debug)]
 pub struct QueryBuilder {
-    query_string_values: HashMap<String, String>,
+    query_string_values: Vec<(String, String)>,
 }
 
 impl QueryBuilder {
     pub fn new() -> QueryBuilder {
-        let query_string_values = HashMap::new();
+        let query_string_values = Vec::new();
 
         QueryBuilder {
             query_string_values,
@@ -14,43 +30,47 @@ impl QueryBuilder {
     }
 
     pub fn content_type_is(mut self, content_type_id: &str) -> QueryBuilder {
-        self.query_string_values
-            .insert("content_type".into(), content_type_id.into());
+        self.insert("content_type".into(), content_type_id.into());
         self
     }
 
     pub fn order_by(mut self, order: &str) -> QueryBuilder {
-        self.query_string_values
-            .insert("order".into(), order.into());
+        self.insert("order".into(), order.into());
         self
     }
 
     pub fn limit(mut self, limit: i32) -> QueryBuilder {
-        self.query_string_values
-            .insert("order".to_string(), limit.to_string());
+        self.insert("limit".to_string(), limit.to_string());
         self
     }
 
     pub fn skip(mut self, skip: i32) -> QueryBuilder {
-        self.query_string_values
-            .insert("skip".to_string(), skip.to_string());
+        self.insert("skip".to_string(), skip.to_string());
         self
     }
 
+    pub(crate) fn skip_value(&self) -> Option<u32> {
+        self.get("skip").and_then(|skip| skip.parse().ok())
+    }
+
     pub fn include(mut self, level: i32) -> QueryBuilder {
-        self.query_string_values
-            .insert("include".into(), level.to_string());
+        self.insert("include".into(), level.to_string());
         self
     }
 
     pub fn locale_is(mut self, value: &str) -> QueryBuilder {
-        self.query_string_values
-            .insert("locale".into(), value.into());
+        self.insert("locale".into(), value.into());
+        self
+    }
+
+    /// Filters assets by the type of their file, e.g. `image`, `video` or `pdfdocument`.
+    pub fn mime_type_group_is(mut self, mime_type_group: &str) -> QueryBuilder {
+        self.insert("mimetype_group".into(), mime_type_group.into());
         self
     }
 
     pub fn field_equals(mut self, field: &str, value: &str) -> QueryBuilder {
-        self.query_string_values.insert(field.into(), value.into());
+        self.insert(field.into(), value.into());
         self
     }
 
@@ -76,7 +96,7 @@ impl QueryBuilder {
 
     pub fn field_exists(mut self, field: &str, must_exist: bool) -> QueryBuilder {
         let key = format!("{}[exists]", field);
-        self.query_string_values.insert(key, must_exist.to_string());
+        self.insert(key, must_exist.to_string());
         self
     }
 
@@ -115,9 +135,32 @@ impl QueryBuilder {
         self
     }
 
+    /// Entries or assets with any of the comma separated tag ids.
+    pub fn tags_include(mut self, tag_ids: &str) -> QueryBuilder {
+        self = self.add_field_restriction("metadata.tags.sys.id", tag_ids, "[in]");
+        self
+    }
+
+    /// Entries or assets with all of the comma separated tag ids.
+    pub fn tags_include_all(mut self, tag_ids: &str) -> QueryBuilder {
+        self = self.add_field_restriction("metadata.tags.sys.id", tag_ids, "[all]");
+        self
+    }
+
+    /// Entries or assets with, or without, any tags.
+    pub fn tags_exist(mut self, must_exist: bool) -> QueryBuilder {
+        self.insert("metadata.tags[exists]".into(), must_exist.to_string());
+        self
+    }
+
     pub fn select_fields(mut self, value: &str) -> QueryBuilder {
-        self.query_string_values
-            .insert("select".into(), value.into());
+        self.insert("select".into(), value.into());
+        self
+    }
+
+    /// Adds a query parameter, keeping any existing values of the same key.
+    pub fn add_query_param(mut self, key: &str, value: &str) -> QueryBuilder {
+        self.query_string_values.push((key.into(), value.into()));
         self
     }
 
@@ -130,9 +173,9 @@ impl QueryBuilder {
             } else {
                 query_string.push('?');
             }
-            query_string.push_str(query_key);
+            query_string.extend(utf8_percent_encode(query_key, QUERY_ENCODE_SET));
             query_string.push('=');
-            query_string.push_str(query_value.as_str());
+            query_string.extend(utf8_percent_encode(query_value, QUERY_ENCODE_SET));
             has_query = true;
         }
 
@@ -146,9 +189,28 @@ impl QueryBuilder {
         operator: &str,
     ) -> QueryBuilder {
         let key = format!("{}{}", field, operator);
-        self.query_string_values.insert(key, value.into());
+        self.insert(key, value.into());
         self
     }
+
+    fn insert(&mut self, key: String, value: String) {
+        if let Some(existing) = self
+            .query_string_values
+            .iter_mut()
+            .find(|(existing_key, _)| *existing_key == key)
+        {
+            existing.1 = value;
+        } else {
+            self.query_string_values.push((key, value));
+        }
+    }
+
+    fn get(&self, key: &str) -> Option<&str> {
+        self.query_string_values
+            .iter()
+            .find(|(existing_key, _)| existing_key == key)
+            .map(|(_, value)| value.as_str())
+    }
 }
 
 impl Default for QueryBuilder {
@@ -0,0 +1,140 @@
+//! Renderers for rich text documents.
+
+pub use html::HtmlRenderer;
+pub use markdown::MarkdownRenderer;
+pub use plain_text::PlainTextRenderer;
+
+use crate::link_resolver::fields_with_sys;
+use crate::models::rich_text::Node;
+use serde_json::Value;
+use std::borrow::Cow;
+
+type EmbeddedHandler<'a> = Box<dyn Fn(&Node, &Value) -> String + 'a>;
+
+/// The `includes` and handlers the renderers render embedded entries and assets with.
+#[derive(Default)]
+pub(crate) struct EmbeddedHandlers<'a> {
+    includes: Option<&'a Value>,
+    entry_handler: Option<EmbeddedHandler<'a>>,
+    asset_handler: Option<EmbeddedHandler<'a>>,
+}
+
+impl<'a> EmbeddedHandlers<'a> {
+    pub(crate) fn resolve<'b>(&'b self, target: &'b Value) -> Cow<'b, Value> {
+        resolve_target(target, self.includes)
+    }
+
+    /// Renders an embedded entry with the entry handler, or as nothing without one.
+    pub(crate) fn render_entry(&self, node: &Node, target: &Value) -> String {
+        match &self.entry_handler {
+            Some(handler) => handler(node, &self.resolve(target)),
+            None => String::new(),
+        }
+    }
+
+    /// Renders an embedded asset with the asset handler, or with `render` without one.
+    pub(crate) fn render_asset<F>(&self, node: &Node, target: &Value, render: F) -> String
+    where
+        F: FnOnce(&Value) -> String,
+    {
+        let target = self.resolve(target);
+        match &self.asset_handler {
+            Some(handler) => handler(node, &target),
+            None => render(&target),
+        }
+    }
+}
+
+/// The builder methods to set the `includes` and embedded handlers of a renderer, and whether to
+/// render tables with `tables`.
+macro_rules! embedded_builder_methods {
+    (tables) => {
+        embedded_builder_methods!();
+
+        /// Whether to render tables, `true` by default.
+        pub fn tables(mut self, tables: bool) -> Self {
+            self.tables = tables;
+            self
+        }
+    };
+    () => {
+        /// The `includes` of the delivery API response, used to look up the entries and assets
+        /// that embedded nodes link to, e.g. `Collection::includes`.
+        pub fn includes(mut self, includes: &'a Value) -> Self {
+            self.embedded.includes = Some(includes);
+            self
+        }
+
+        /// Renders `embedded-entry-block` and `embedded-entry-inline` nodes, and the
+        /// `embedded-resource-block` and `embedded-resource-inline` nodes of entries of other
+        /// spaces. The handler gets the node and the linked entry as its fields with `sys`, or
+        /// the link itself if the entry is not included.
+        pub fn embedded_entry_handler<F>(mut self, handler: F) -> Self
+        where
+            F: Fn(&Node, &Value) -> String + 'a,
+        {
+            self.embedded.entry_handler = Some(Box::new(handler));
+            self
+        }
+
+        /// Renders `embedded-asset-block` nodes. The handler gets the node and the linked asset
+        /// as its fields with `sys`, or the link itself if the asset is not included.
+        pub fn embedded_asset_handler<F>(mut self, handler: F) -> Self
+        where
+            F: Fn(&Node, &Value) -> String + 'a,
+        {
+            self.embedded.asset_handler = Some(Box::new(handler));
+            self
+        }
+    };
+}
+
+// After the macros, which the renderers use
+mod html;
+mod markdown;
+mod plain_text;
+
+/// Returns the entry or asset a rich text `data.target` links to as its fields with `sys`, like
+/// the delivery client resolves links. The entry or asset is looked up in the `includes` of a
+/// delivery API response if the target is a link, which is returned as is if it cannot be found.
+fn resolve_target<'a>(target: &'a Value, includes: Option<&'a Value>) -> Cow<'a, Value> {
+    let entry = if target["sys"]["type"] == "Link" {
+        let link_type = target["sys"]["linkType"].as_str().unwrap_or_default();
+        let id = &target["sys"]["id"];
+        match includes
+            .and_then(|includes| includes[link_type].as_array())
+            .and_then(|included| included.iter().find(|item| &item["sys"]["id"] == id))
+        {
+            Some(entry) => entry,
+            None => return Cow::Borrowed(target),
+        }
+    } else {
+        target
+    };
+    if entry.get("fields").is_some() {
+        Cow::Owned(fields_with_sys(entry.clone()))
+    } else {
+        // Already resolved by the delivery client
+        Cow::Borrowed(entry)
+    }
+}
+
+pub(crate) fn asset_url(asset: &Value) -> Option<&str> {
+    asset["file"]["url"].as_str()
+}
+
+/// Prefixes the first line of `text` with `first` and the following lines with `rest`.
+pub(crate) fn indent(text: &str, first: &str, rest: &str) -> String {
+    text.lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let prefix = if i == 0 { first } else { rest };
+            if line.is_empty() {
+                prefix.trim_end().to_string()
+            } else {
+                format!("{}{}", prefix, line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,175 @@
+use crate::models::rich_text::{Document, MarkType, Node, Text};
+use crate::renderers::{asset_url, EmbeddedHandlers};
+use serde_json::Value;
+use std::collections::HashMap;
+
+type NodeHandler<'a> = Box<dyn Fn(&Node, &str) -> String + 'a>;
+type MarkHandler<'a> = Box<dyn Fn(&str) -> String + 'a>;
+
+/// Renders rich text documents to HTML.
+///
+/// Text and attributes are escaped. The HTML of any node type and mark can be replaced with
+/// `node_handler` and `mark_handler`, and embedded entries and assets are rendered with
+/// `embedded_entry_handler` and `embedded_asset_handler`.
+///
+/// ```
+/// use contentful::models::rich_text::{Document, MarkType};
+/// use contentful::renderers::HtmlRenderer;
+/// # use serde_json::json;
+/// # let document: Document = serde_json::from_value(json!({
+/// #     "nodeType": "document", "data": {},
+/// #     "content": [{ "nodeType": "paragraph", "data": {}, "content": [
+/// #         { "nodeType": "text", "value": "Hello", "marks": [{ "type": "bold" }], "data": {} }
+/// #     ] }]
+/// # })).unwrap();
+///
+/// let html = HtmlRenderer::new()
+///     .node_handler("paragraph", |_node, inner| format!("<p class=\"lead\">{}</p>", inner))
+///     .mark_handler(MarkType::Bold, |text| format!("<strong>{}</strong>", text))
+///     .render(&document);
+/// assert_eq!(html, "<p class=\"lead\"><strong>Hello</strong></p>");
+/// ```
+#[derive(Default)]
+pub struct HtmlRenderer<'a> {
+    node_handlers: HashMap<String, NodeHandler<'a>>,
+    mark_handlers: HashMap<MarkType, MarkHandler<'a>>,
+    embedded: EmbeddedHandlers<'a>,
+}
+
+impl<'a> HtmlRenderer<'a> {
+    pub fn new() -> HtmlRenderer<'a> {
+        HtmlRenderer::default()
+    }
+
+    /// Replaces the HTML of nodes of `node_type`, e.g. `heading-1`. The handler gets the node
+    /// and the HTML of its content.
+    pub fn node_handler<F>(mut self, node_type: &str, handler: F) -> Self
+    where
+        F: Fn(&Node, &str) -> String + 'a,
+    {
+        self.node_handlers
+            .insert(node_type.into(), Box::new(handler));
+        self
+    }
+
+    /// Replaces the HTML of a mark. The handler gets the escaped text.
+    pub fn mark_handler<F>(mut self, mark_type: MarkType, handler: F) -> Self
+    where
+        F: Fn(&str) -> String + 'a,
+    {
+        self.mark_handlers.insert(mark_type, Box::new(handler));
+        self
+    }
+
+    embedded_builder_methods!();
+
+    pub fn render(&self, document: &Document) -> String {
+        self.render_nodes(&document.content)
+    }
+
+    pub fn render_nodes(&self, nodes: &[Node]) -> String {
+        nodes.iter().map(|node| self.render_node(node)).collect()
+    }
+
+    pub fn render_node(&self, node: &Node) -> String {
+        let inner = match node {
+            Node::Text(text) => return self.render_text(text),
+            _ => self.render_nodes(node.content()),
+        };
+        if let Some(handler) = self.node_handlers.get(node.node_type()) {
+            return handler(node, &inner);
+        }
+
+        match node {
+            Node::Document(_) => inner,
+            Node::Paragraph(_) => format!("<p>{}</p>", inner),
+            Node::Heading1(_) => format!("<h1>{}</h1>", inner),
+            Node::Heading2(_) => format!("<h2>{}</h2>", inner),
+            Node::Heading3(_) => format!("<h3>{}</h3>", inner),
+            Node::Heading4(_) => format!("<h4>{}</h4>", inner),
+            Node::Heading5(_) => format!("<h5>{}</h5>", inner),
+            Node::Heading6(_) => format!("<h6>{}</h6>", inner),
+            Node::OrderedList(_) => format!("<ol>{}</ol>", inner),
+            Node::UnorderedList(_) => format!("<ul>{}</ul>", inner),
+            Node::ListItem(_) => format!("<li>{}</li>", inner),
+            Node::Blockquote(_) => format!("<blockquote>{}</blockquote>", inner),
+            Node::Hr(_) => "<hr/>".into(),
+            Node::Table(_) => format!("<table>{}</table>", inner),
+            Node::TableRow(_) => format!("<tr>{}</tr>", inner),
+            Node::TableHeaderCell(_) => format!("<th>{}</th>", inner),
+            Node::TableCell(_) => format!("<td>{}</td>", inner),
+            Node::Hyperlink(hyperlink) => {
+                format!("<a href=\"{}\">{}</a>", escape(&hyperlink.data.uri), inner)
+            }
+            Node::EmbeddedEntryBlock(embedded)
+            | Node::EmbeddedEntryInline(embedded)
+            | Node::EmbeddedResourceBlock(embedded)
+            | Node::EmbeddedResourceInline(embedded) => {
+                self.embedded.render_entry(node, &embedded.data.target)
+            }
+            Node::EmbeddedAssetBlock(embedded) => {
+                self.embedded
+                    .render_asset(node, &embedded.data.target, render_asset)
+            }
+            Node::EntryHyperlink(_) | Node::ResourceHyperlink(_) | Node::Unknown(_) => inner,
+            Node::AssetHyperlink(embedded) => {
+                let target = self.embedded.resolve(&embedded.data.target);
+                match asset_url(&target) {
+                    Some(url) => format!("<a href=\"{}\">{}</a>", escape(url), inner),
+                    None => inner,
+                }
+            }
+            Node::Text(_) => unreachable!(),
+        }
+    }
+
+    fn render_text(&self, text: &Text) -> String {
+        let mut html = escape(&text.value).replace('\n', "<br/>");
+        for mark in &text.marks {
+            html = match self.mark_handlers.get(&mark.mark_type) {
+                Some(handler) => handler(&html),
+                None => match &mark.mark_type {
+                    MarkType::Bold => format!("<b>{}</b>", html),
+                    MarkType::Italic => format!("<i>{}</i>", html),
+                    MarkType::Underline => format!("<u>{}</u>", html),
+                    MarkType::Code => format!("<code>{}</code>", html),
+                    MarkType::Superscript => format!("<sup>{}</sup>", html),
+                    MarkType::Subscript => format!("<sub>{}</sub>", html),
+                    MarkType::Strikethrough => format!("<s>{}</s>", html),
+                    MarkType::Other(_) => html,
+                },
+            };
+        }
+        html
+    }
+}
+
+/// An image for image assets, and a link for other files.
+fn render_asset(asset: &Value) -> String {
+    let url = match asset_url(asset) {
+        Some(url) => url,
+        None => return String::new(),
+    };
+    let title = asset["title"].as_str().unwrap_or_default();
+    let content_type = asset["file"]["contentType"].as_str().unwrap_or_default();
+    if content_type.starts_with("image/") {
+        format!("<img src=\"{}\" alt=\"{}\"/>", escape(url), escape(title))
+    } else {
+        format!("<a href=\"{}\">{}</a>", escape(url), escape(title))
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
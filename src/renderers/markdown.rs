@@ -0,0 +1,287 @@
+use crate::models::rich_text::{Document, MarkType, Node, Text};
+use crate::renderers::{asset_url, indent, EmbeddedHandlers};
+use serde_json::Value;
+
+/// Renders rich text documents to CommonMark, with tables as GitHub flavored Markdown tables.
+///
+/// Embedded assets are rendered as images and embedded entries are left out, unless rendered
+/// with `embedded_entry_handler` and `embedded_asset_handler`.
+pub struct MarkdownRenderer<'a> {
+    embedded: EmbeddedHandlers<'a>,
+    tables: bool,
+}
+
+impl<'a> Default for MarkdownRenderer<'a> {
+    fn default() -> Self {
+        MarkdownRenderer {
+            embedded: EmbeddedHandlers::default(),
+            tables: true,
+        }
+    }
+}
+
+impl<'a> MarkdownRenderer<'a> {
+    pub fn new() -> MarkdownRenderer<'a> {
+        MarkdownRenderer::default()
+    }
+
+    embedded_builder_methods!(tables);
+
+    pub fn render(&self, document: &Document) -> String {
+        self.render_blocks(&document.content, "\n\n")
+    }
+
+    fn render_blocks(&self, nodes: &[Node], separator: &str) -> String {
+        nodes
+            .iter()
+            .map(|node| self.render_block(node))
+            .filter(|block| !block.is_empty())
+            .collect::<Vec<_>>()
+            .join(separator)
+    }
+
+    fn render_block(&self, node: &Node) -> String {
+        match node {
+            Node::Document(block) | Node::ListItem(block) => {
+                self.render_blocks(&block.content, "\n\n")
+            }
+            Node::Paragraph(block) => escape_block_start(&self.render_inlines(&block.content)),
+            Node::Heading1(block) => format!("# {}", self.render_inlines(&block.content)),
+            Node::Heading2(block) => format!("## {}", self.render_inlines(&block.content)),
+            Node::Heading3(block) => format!("### {}", self.render_inlines(&block.content)),
+            Node::Heading4(block) => format!("#### {}", self.render_inlines(&block.content)),
+            Node::Heading5(block) => format!("##### {}", self.render_inlines(&block.content)),
+            Node::Heading6(block) => format!("###### {}", self.render_inlines(&block.content)),
+            Node::UnorderedList(block) => block
+                .content
+                .iter()
+                .map(|item| indent(&self.render_list_item(item), "- ", "  "))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Node::OrderedList(block) => block
+                .content
+                .iter()
+                .enumerate()
+                .map(|(i, item)| {
+                    let marker = format!("{}. ", i + 1);
+                    let rest = " ".repeat(marker.len());
+                    indent(&self.render_list_item(item), &marker, &rest)
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Node::Blockquote(block) => {
+                indent(&self.render_blocks(&block.content, "\n\n"), "> ", "> ")
+            }
+            Node::Hr(_) => "---".into(),
+            Node::Table(block) if self.tables => self.render_table(&block.content),
+            Node::Table(_) => String::new(),
+            Node::EmbeddedEntryBlock(embedded) | Node::EmbeddedResourceBlock(embedded) => {
+                self.embedded.render_entry(node, &embedded.data.target)
+            }
+            Node::EmbeddedAssetBlock(embedded) => {
+                self.embedded
+                    .render_asset(node, &embedded.data.target, render_asset)
+            }
+            Node::Unknown(unknown) => self.render_blocks(&unknown.content, "\n\n"),
+            _ => self.render_inline(node),
+        }
+    }
+
+    /// Paragraphs of list items are separated by blank lines, which would otherwise continue the
+    /// paragraph before. Nested lists follow without one, so that lists stay tight.
+    fn render_list_item(&self, node: &Node) -> String {
+        let mut markdown = String::new();
+        for block in node.content() {
+            let rendered = self.render_block(block);
+            if rendered.is_empty() {
+                continue;
+            }
+            if !markdown.is_empty() {
+                match block {
+                    Node::OrderedList(_) | Node::UnorderedList(_) => markdown.push('\n'),
+                    _ => markdown.push_str("\n\n"),
+                }
+            }
+            markdown.push_str(&rendered);
+        }
+        markdown
+    }
+
+    fn render_table(&self, rows: &[Node]) -> String {
+        let rows = rows
+            .iter()
+            .map(|row| {
+                row.content()
+                    .iter()
+                    .map(|cell| {
+                        escape_pipes(&self.render_blocks(cell.content(), " ").replace('\n', " "))
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let columns = rows.iter().map(|row| row.len()).max().unwrap_or_default();
+        if columns == 0 {
+            return String::new();
+        }
+
+        let render_row = |cells: &[String]| {
+            let mut cells = cells.to_vec();
+            cells.resize(columns, String::new());
+            format!("| {} |", cells.join(" | "))
+        };
+        let mut lines = vec![
+            render_row(&rows[0]),
+            render_row(&vec!["---".into(); columns]),
+        ];
+        lines.extend(rows[1..].iter().map(|row| render_row(row)));
+        lines.join("\n")
+    }
+
+    fn render_inlines(&self, nodes: &[Node]) -> String {
+        nodes.iter().map(|node| self.render_inline(node)).collect()
+    }
+
+    fn render_inline(&self, node: &Node) -> String {
+        match node {
+            Node::Text(text) => render_text(text),
+            Node::Hyperlink(hyperlink) => format!(
+                "[{}]({})",
+                self.render_inlines(&hyperlink.content),
+                escape_url(&hyperlink.data.uri)
+            ),
+            Node::EntryHyperlink(embedded) | Node::ResourceHyperlink(embedded) => {
+                self.render_inlines(&embedded.content)
+            }
+            Node::AssetHyperlink(embedded) => {
+                let inner = self.render_inlines(&embedded.content);
+                let target = self.embedded.resolve(&embedded.data.target);
+                match asset_url(&target) {
+                    Some(url) => format!("[{}]({})", inner, escape_url(url)),
+                    None => inner,
+                }
+            }
+            Node::EmbeddedEntryInline(embedded) | Node::EmbeddedResourceInline(embedded) => {
+                self.embedded.render_entry(node, &embedded.data.target)
+            }
+            _ => self.render_block(node),
+        }
+    }
+}
+
+fn render_text(text: &Text) -> String {
+    let (leading, mut markdown, trailing) = if text.has_mark(&MarkType::Code) {
+        (String::new(), render_code(&text.value), String::new())
+    } else {
+        // Emphasis must not start or end with whitespace, so it is moved outside of the markers
+        let value = escape(&text.value).replace('\n', "\\\n");
+        let trimmed = value.trim();
+        if trimmed.is_empty() {
+            return value;
+        }
+        let start = value.find(trimmed).unwrap_or_default();
+        (
+            value[..start].to_string(),
+            trimmed.to_string(),
+            value[start + trimmed.len()..].to_string(),
+        )
+    };
+
+    for mark in &text.marks {
+        markdown = match &mark.mark_type {
+            MarkType::Bold => format!("**{}**", markdown),
+            MarkType::Italic => format!("_{}_", markdown),
+            MarkType::Strikethrough => format!("~~{}~~", markdown),
+            MarkType::Underline => format!("<u>{}</u>", markdown),
+            MarkType::Superscript => format!("<sup>{}</sup>", markdown),
+            MarkType::Subscript => format!("<sub>{}</sub>", markdown),
+            MarkType::Code | MarkType::Other(_) => markdown,
+        };
+    }
+    format!("{}{}{}", leading, markdown, trailing)
+}
+
+/// Renders a code span with a fence longer than any run of backticks in the code.
+fn render_code(code: &str) -> String {
+    let longest_run = code
+        .split(|c| c != '`')
+        .map(|run| run.len())
+        .max()
+        .unwrap_or_default();
+    let fence = "`".repeat(longest_run + 1);
+    // One space is stripped from both ends of code that starts and ends with one, so that code
+    // can start or end with a backtick
+    let padded = code.starts_with('`')
+        || code.ends_with('`')
+        || (code.starts_with(' ') && code.ends_with(' ') && !code.trim().is_empty());
+    if padded {
+        format!("{} {} {}", fence, code, fence)
+    } else {
+        format!("{}{}{}", fence, code, fence)
+    }
+}
+
+fn render_asset(asset: &Value) -> String {
+    match asset_url(asset) {
+        Some(url) => {
+            let title = asset["title"].as_str().unwrap_or_default();
+            format!("![{}]({})", escape(title), escape_url(url))
+        }
+        None => String::new(),
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(
+            c,
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '|' | '~' | '!' | '&'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Escapes what would start a list or heading at the start of the lines of a paragraph, e.g.
+/// `1. ` or `- `. `#` is escaped anywhere.
+fn escape_block_start(paragraph: &str) -> String {
+    paragraph
+        .split('\n')
+        .map(|line| {
+            // Up to three spaces of indentation still start a list
+            let (indentation, line) =
+                line.split_at(line.len() - line.trim_start_matches(' ').len());
+            let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+            if line.starts_with('-') || line.starts_with('+') || line.starts_with('=') {
+                format!("{}\\{}", indentation, line)
+            } else if digits > 0 && matches!(line[digits..].chars().next(), Some('.') | Some(')')) {
+                format!("{}{}\\{}", indentation, &line[..digits], &line[digits..])
+            } else {
+                format!("{}{}", indentation, line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Escapes the `|` of a table cell that are not escaped yet, e.g. in code or URLs.
+fn escape_pipes(cell: &str) -> String {
+    let mut escaped = String::with_capacity(cell.len());
+    let mut backslashes = 0;
+    for c in cell.chars() {
+        if c == '|' && backslashes % 2 == 0 {
+            escaped.push('\\');
+        }
+        backslashes = if c == '\\' { backslashes + 1 } else { 0 };
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn escape_url(url: &str) -> String {
+    url.replace(' ', "%20")
+        .replace('(', "%28")
+        .replace(')', "%29")
+}
@@ -0,0 +1,107 @@
+use crate::models::rich_text::{Document, Node};
+use crate::renderers::{indent, EmbeddedHandlers};
+use serde_json::Value;
+
+/// Renders rich text documents to plain text, e.g. for search indexes or text emails.
+///
+/// Blocks are separated by blank lines, list items are prefixed with `-` or their number and
+/// table cells are separated by tabs. Embedded assets are rendered as their title and embedded
+/// entries are left out, unless rendered with `embedded_entry_handler` and
+/// `embedded_asset_handler`.
+pub struct PlainTextRenderer<'a> {
+    embedded: EmbeddedHandlers<'a>,
+    tables: bool,
+}
+
+impl<'a> Default for PlainTextRenderer<'a> {
+    fn default() -> Self {
+        PlainTextRenderer {
+            embedded: EmbeddedHandlers::default(),
+            tables: true,
+        }
+    }
+}
+
+impl<'a> PlainTextRenderer<'a> {
+    pub fn new() -> PlainTextRenderer<'a> {
+        PlainTextRenderer::default()
+    }
+
+    embedded_builder_methods!(tables);
+
+    pub fn render(&self, document: &Document) -> String {
+        self.render_blocks(&document.content, "\n\n")
+    }
+
+    fn render_blocks(&self, nodes: &[Node], separator: &str) -> String {
+        nodes
+            .iter()
+            .map(|node| self.render_node(node))
+            .filter(|block| !block.is_empty())
+            .collect::<Vec<_>>()
+            .join(separator)
+    }
+
+    fn render_node(&self, node: &Node) -> String {
+        match node {
+            Node::Text(text) => text.value.clone(),
+            Node::Paragraph(block)
+            | Node::Heading1(block)
+            | Node::Heading2(block)
+            | Node::Heading3(block)
+            | Node::Heading4(block)
+            | Node::Heading5(block)
+            | Node::Heading6(block) => self.render_blocks(&block.content, ""),
+            Node::Hyperlink(hyperlink) => self.render_blocks(&hyperlink.content, ""),
+            Node::EntryHyperlink(embedded)
+            | Node::AssetHyperlink(embedded)
+            | Node::ResourceHyperlink(embedded) => self.render_blocks(&embedded.content, ""),
+            Node::UnorderedList(block) => block
+                .content
+                .iter()
+                .map(|item| indent(&self.render_blocks(item.content(), "\n"), "- ", "  "))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Node::OrderedList(block) => block
+                .content
+                .iter()
+                .enumerate()
+                .map(|(i, item)| {
+                    let marker = format!("{}. ", i + 1);
+                    let rest = " ".repeat(marker.len());
+                    indent(&self.render_blocks(item.content(), "\n"), &marker, &rest)
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Node::Hr(_) => String::new(),
+            Node::Table(_) if !self.tables => String::new(),
+            Node::Table(block) => self.render_blocks(&block.content, "\n"),
+            // Empty cells are kept so that the columns line up
+            Node::TableRow(block) => block
+                .content
+                .iter()
+                .map(|cell| self.render_node(cell))
+                .collect::<Vec<_>>()
+                .join("\t"),
+            Node::TableHeaderCell(block) | Node::TableCell(block) => {
+                self.render_blocks(&block.content, " ").replace('\n', " ")
+            }
+            Node::EmbeddedEntryBlock(embedded)
+            | Node::EmbeddedEntryInline(embedded)
+            | Node::EmbeddedResourceBlock(embedded)
+            | Node::EmbeddedResourceInline(embedded) => {
+                self.embedded.render_entry(node, &embedded.data.target)
+            }
+            Node::EmbeddedAssetBlock(embedded) => {
+                self.embedded
+                    .render_asset(node, &embedded.data.target, |target| {
+                        target["title"].as_str().unwrap_or_default().into()
+                    })
+            }
+            Node::Document(block) | Node::ListItem(block) | Node::Blockquote(block) => {
+                self.render_blocks(&block.content, "\n\n")
+            }
+            Node::Unknown(unknown) => self.render_blocks(&unknown.content, "\n\n"),
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use reqwest::{Method, StatusCode};
+use std::time::Duration;
+
+/// Controls how requests are retried when Contentful responds with
+/// `429 Too Many Requests` or a `5xx` server error.
+///
+/// Requests that are not idempotent, e.g. the `POST` that creates an entry, are only retried
+/// when rate limited by default, as Contentful may have created the entry before failing.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    pub(crate) max_retries: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    jitter: bool,
+    respect_rate_limit_reset: bool,
+    retry_on_server_errors: bool,
+    retry_non_idempotent_on_server_errors: bool,
+}
+
+impl RetryPolicy {
+    pub fn new() -> RetryPolicy {
+        RetryPolicy {
+            max_retries: 5,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            jitter: true,
+            respect_rate_limit_reset: true,
+            retry_on_server_errors: true,
+            retry_non_idempotent_on_server_errors: false,
+        }
+    }
+
+    /// A policy that never retries.
+    pub fn none() -> RetryPolicy {
+        RetryPolicy::new().max_retries(0)
+    }
+
+    pub fn max_retries(mut self, max_retries: u32) -> RetryPolicy {
+        self.max_retries = max_retries;
+        self
+    }
+
+    pub fn initial_backoff(mut self, initial_backoff: Duration) -> RetryPolicy {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    pub fn max_backoff(mut self, max_backoff: Duration) -> RetryPolicy {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Randomizes each backoff between half and all of its computed value.
+    pub fn jitter(mut self, jitter: bool) -> RetryPolicy {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Waits for the number of seconds in `X-Contentful-RateLimit-Reset` instead of
+    /// the computed backoff when the header is present.
+    pub fn respect_rate_limit_reset(mut self, respect_rate_limit_reset: bool) -> RetryPolicy {
+        self.respect_rate_limit_reset = respect_rate_limit_reset;
+        self
+    }
+
+    pub fn retry_on_server_errors(mut self, retry_on_server_errors: bool) -> RetryPolicy {
+        self.retry_on_server_errors = retry_on_server_errors;
+        self
+    }
+
+    /// Also retries requests that are not idempotent, e.g. `POST`, on server errors, which can
+    /// create duplicates.
+    pub fn retry_non_idempotent_on_server_errors(
+        mut self,
+        retry_non_idempotent_on_server_errors: bool,
+    ) -> RetryPolicy {
+        self.retry_non_idempotent_on_server_errors = retry_non_idempotent_on_server_errors;
+        self
+    }
+
+    pub fn should_retry(&self, method: &Method, status: StatusCode) -> bool {
+        let idempotent = matches!(
+            *method,
+            Method::GET | Method::HEAD | Method::PUT | Method::DELETE | Method::OPTIONS
+        );
+        status == StatusCode::TOO_MANY_REQUESTS
+            || (self.retry_on_server_errors
+                && (idempotent || self.retry_non_idempotent_on_server_errors)
+                && status.is_server_error())
+    }
+
+    /// The delay before the given retry, starting at 0 for the first retry.
+    pub fn delay_for(&self, retry: u32, rate_limit_reset: Option<u64>) -> Duration {
+        if self.respect_rate_limit_reset {
+            if let Some(reset) = rate_limit_reset {
+                return Duration::from_secs(reset);
+            }
+        }
+
+        let backoff = self
+            .initial_backoff
+            .checked_mul(2u32.saturating_pow(retry))
+            .unwrap_or(self.max_backoff)
+            .min(self.max_backoff);
+
+        if self.jitter {
+            let half = backoff / 2;
+            half + half.mul_f64(fastrand::f64())
+        } else {
+            backoff
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,105 @@
+use contentful::{ContentfulClient, ContentfulManagementClient, Error};
+use serde_json::json;
+use std::time::Duration;
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn builder_uses_base_url_environment_and_user_agent() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(
+            "/spaces/space-id/environments/staging/entries/entry-id",
+        ))
+        .and(header("authorization", "Bearer token"))
+        .and(header("user-agent", "my-app/1.0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "sys": { "id": "entry-id", "type": "Entry" },
+            "fields": { "name": "Saju" }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let contentful_client = ContentfulClient::builder("token", "space-id")
+        .base_url(&server.uri())
+        .environment("staging")
+        .user_agent("my-app/1.0")
+        .timeout(Duration::from_secs(5))
+        .build();
+    let actual = contentful_client
+        .get_entry_json_value("entry-id")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(actual["fields"]["name"], "Saju");
+}
+
+#[tokio::test]
+async fn builder_shares_supplied_client() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(
+            "/spaces/space-id/environments/master/entries/entry-id",
+        ))
+        .and(header("x-custom", "custom"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "sys": { "id": "entry-id", "version": 3 },
+            "fields": { "name": { "en-US": "Saju" } }
+        })))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert("x-custom", "custom".parse().unwrap());
+    let client = reqwest::Client::builder()
+        .default_headers(headers)
+        .build()
+        .unwrap();
+    let contentful_client = ContentfulClient::builder("token", "space-id")
+        .base_url(&server.uri())
+        .client(client.clone())
+        .build();
+    let management_client = ContentfulManagementClient::builder("token", "space-id")
+        .base_url(&server.uri())
+        .client(client)
+        .build();
+
+    let entry = contentful_client
+        .get_entry_json_value("entry-id")
+        .await
+        .unwrap();
+    assert!(entry.is_some());
+    let entry = management_client
+        .get_entry("entry-id")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(entry.sys.version, Some(3));
+}
+
+#[tokio::test]
+async fn non_success_response_returns_error() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(401).set_body_json(json!({
+            "sys": { "type": "Error", "id": "AccessTokenInvalid" },
+            "message": "The access token you sent could not be found or is invalid.",
+            "requestId": "request-id"
+        })))
+        .mount(&server)
+        .await;
+
+    let contentful_client = ContentfulClient::builder("token", "space-id")
+        .base_url(&server.uri())
+        .build();
+    let actual = contentful_client.get_entry_json_value("entry-id").await;
+    match actual {
+        Err(Error::Authentication(error)) => {
+            assert_eq!(error.id.as_deref(), Some("AccessTokenInvalid"));
+            assert_eq!(error.request_id.as_deref(), Some("request-id"));
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
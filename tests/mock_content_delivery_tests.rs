@@ -0,0 +1,1105 @@
+use contentful::{
+    models::rich_text::{Document, Node},
+    models::Asset,
+    models::Link,
+    models::LinkReference,
+    models::Metadata,
+    models::SystemProperties,
+    renderers::HtmlRenderer,
+    ContentfulClient, Error, QueryBuilder,
+};
+use futures::TryStreamExt;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn get_entry_works() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(
+            "/spaces/space-id/environments/master/entries/3YrHEsZ9iUsEQOu6IQsI6k",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(person_entry()))
+        .mount(&server)
+        .await;
+
+    let contentful_client = client(&server);
+    let actual = contentful_client
+        .get_entry::<SimplePerson>("3YrHEsZ9iUsEQOu6IQsI6k")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(actual.name, "Saju");
+    assert_eq!(actual.sys.id, "3YrHEsZ9iUsEQOu6IQsI6k");
+}
+
+#[tokio::test]
+async fn get_entry_returns_none_when_not_found() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+            "sys": { "type": "Error", "id": "NotFound" },
+            "message": "The resource could not be found."
+        })))
+        .mount(&server)
+        .await;
+
+    let contentful_client = client(&server);
+    let actual = contentful_client
+        .get_entry::<SimplePerson>("missing")
+        .await
+        .unwrap();
+    assert!(actual.is_none());
+}
+
+#[tokio::test]
+async fn get_entries_returns_not_found_error() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+            "sys": { "type": "Error", "id": "NotFound" },
+            "message": "The resource could not be found."
+        })))
+        .mount(&server)
+        .await;
+
+    let contentful_client = client(&server);
+    let actual = contentful_client.get_entries::<SimplePerson>(None).await;
+    match actual {
+        Err(Error::NotFound(error)) => {
+            assert_eq!(error.id.as_deref(), Some("NotFound"));
+            assert_eq!(
+                error.message.as_deref(),
+                Some("The resource could not be found.")
+            );
+        }
+        _ => panic!("expected a not found error"),
+    }
+    assert!(matches!(
+        contentful_client.get_locales().await,
+        Err(Error::NotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn get_entries_by_type_resolves_links() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/spaces/space-id/environments/master/entries"))
+        .and(query_param("content_type", "person"))
+        .and(query_param("fields.name", "Saju"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "sys": { "type": "Array" },
+            "total": 1,
+            "skip": 0,
+            "limit": 100,
+            "items": [person_entry()],
+            "includes": {
+                "Entry": [{
+                    "sys": { "id": "product-id", "type": "Entry" },
+                    "fields": { "title": "Contentful" }
+                }],
+                "Asset": [{
+                    "sys": { "id": "asset-id", "type": "Asset" },
+                    "fields": {
+                        "title": "Saju",
+                        "file": {
+                            "fileName": "saju.png",
+                            "contentType": "image/png",
+                            "url": "//images.ctfassets.net/saju.png",
+                            "details": { "size": 1024, "image": { "width": 10, "height": 20 } }
+                        }
+                    }
+                }]
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let contentful_client = client(&server);
+    let query_builder = QueryBuilder::new().field_equals("fields.name", "Saju");
+    let actual = contentful_client
+        .get_entries_by_type::<Person>("person", Some(query_builder))
+        .await
+        .unwrap();
+    assert_eq!(actual.len(), 1);
+    assert_eq!(actual[0].name, "Saju");
+    assert_eq!(
+        actual[0].favorite_product.as_ref().unwrap().title,
+        "Contentful"
+    );
+    assert_eq!(actual[0].image.as_ref().unwrap().file.file_name, "saju.png");
+}
+
+#[tokio::test]
+async fn get_entries_by_query_string_works() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/spaces/space-id/environments/master/entries"))
+        .and(query_param("content_type", "person"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "sys": { "type": "Array" },
+            "total": 1,
+            "skip": 0,
+            "limit": 100,
+            "items": [{
+                "sys": { "id": "3YrHEsZ9iUsEQOu6IQsI6k", "type": "Entry" },
+                "fields": { "name": "Saju", "title": "Mr", "tags": ["a", "b"] }
+            }]
+        })))
+        .mount(&server)
+        .await;
+
+    let contentful_client = client(&server);
+    let actual = contentful_client
+        .get_entries_by_query_string::<Value>("?content_type=person")
+        .await
+        .unwrap();
+    assert_eq!(actual[0]["name"], "Saju");
+    assert_eq!(actual[0]["tags"], json!(["a", "b"]));
+}
+
+#[tokio::test]
+async fn preview_removes_unresolved_links() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/spaces/space-id/environments/master/entries"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "sys": { "type": "Array" },
+            "total": 1,
+            "skip": 0,
+            "limit": 100,
+            "items": [{
+                "sys": { "id": "draft-id", "type": "Entry" },
+                "fields": {
+                    "name": "Draft",
+                    "title": "Mr",
+                    "favoriteProduct": {
+                        "sys": { "type": "Link", "linkType": "Entry", "id": "archived-id" }
+                    },
+                    "interestedProducts": [
+                        { "sys": { "type": "Link", "linkType": "Entry", "id": "product-id" } },
+                        { "sys": { "type": "Link", "linkType": "Entry", "id": "deleted-id" } }
+                    ]
+                }
+            }],
+            "includes": {
+                "Entry": [{ "sys": { "id": "product-id", "type": "Entry" } }]
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let contentful_client = ContentfulClient::builder("preview-token", "space-id")
+        .base_url(&server.uri())
+        .preview(true)
+        .build();
+    let actual = contentful_client.get_entries::<Value>(None).await.unwrap();
+    assert_eq!(actual[0]["favoriteProduct"], Value::Null);
+    assert_eq!(
+        actual[0]["interestedProducts"],
+        json!([{ "sys": { "id": "product-id", "type": "Entry" } }])
+    );
+
+    let contentful_client = client(&server);
+    let actual = contentful_client.get_entries::<Value>(None).await.unwrap();
+    assert_eq!(actual[0]["favoriteProduct"]["sys"]["id"], "archived-id");
+    assert_eq!(
+        actual[0]["interestedProducts"][1]["sys"]["id"],
+        "deleted-id"
+    );
+}
+
+#[tokio::test]
+async fn get_entries_resolves_rich_text_links() {
+    let server = MockServer::start().await;
+    let link = |link_type: &str, id: &str| json!({ "sys": { "type": "Link", "linkType": link_type, "id": id } });
+    Mock::given(method("GET"))
+        .and(path("/spaces/space-id/environments/master/entries"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "sys": { "type": "Array" },
+            "total": 1,
+            "skip": 0,
+            "limit": 100,
+            "items": [{
+                "sys": { "id": "article-id", "type": "Entry" },
+                "fields": {
+                    "title": "Article",
+                    "body": {
+                        "nodeType": "document",
+                        "data": {},
+                        "content": [
+                            {
+                                "nodeType": "embedded-entry-block",
+                                "data": { "target": link("Entry", "product-id") },
+                                "content": []
+                            },
+                            {
+                                "nodeType": "paragraph",
+                                "data": {},
+                                "content": [{
+                                    "nodeType": "asset-hyperlink",
+                                    "data": { "target": link("Asset", "asset-id") },
+                                    "content": [{ "nodeType": "text", "value": "Saju", "marks": [], "data": {} }]
+                                }]
+                            },
+                            {
+                                "nodeType": "embedded-entry-block",
+                                "data": { "target": link("Entry", "deleted-id") },
+                                "content": []
+                            }
+                        ]
+                    }
+                }
+            }],
+            "includes": {
+                "Entry": [{
+                    "sys": { "id": "product-id", "type": "Entry" },
+                    "fields": { "title": "Contentful" }
+                }],
+                "Asset": [asset()]
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let contentful_client = client(&server);
+    let actual = contentful_client
+        .get_entries::<Article>(None)
+        .await
+        .unwrap();
+    let body = &actual[0].body;
+    match &body.content[0] {
+        Node::EmbeddedEntryBlock(embedded) => {
+            assert_eq!(embedded.data.target["title"], "Contentful");
+            assert_eq!(embedded.data.target["sys"]["id"], "product-id");
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+    match &body.content[1].content()[0] {
+        Node::AssetHyperlink(embedded) => {
+            assert_eq!(embedded.data.target["file"]["fileName"], "saju.png")
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+    assert_eq!(body.content.len(), 3);
+    let html = HtmlRenderer::new()
+        .embedded_entry_handler(|_node, entry| {
+            format!(
+                "<aside id={}>{}</aside>",
+                entry["sys"]["id"], entry["title"]
+            )
+        })
+        .render(body);
+    assert!(html.starts_with("<aside id=\"product-id\">\"Contentful\"</aside>"));
+
+    let contentful_client = ContentfulClient::builder("token", "space-id")
+        .base_url(&server.uri())
+        .remove_unresolved(true)
+        .build();
+    let actual = contentful_client
+        .get_entries::<Article>(None)
+        .await
+        .unwrap();
+    assert_eq!(actual[0].body.content.len(), 2);
+}
+
+#[tokio::test]
+async fn get_entries_keeps_circular_and_too_deep_links() {
+    let server = MockServer::start().await;
+    let link = |id: &str| json!({ "sys": { "type": "Link", "linkType": "Entry", "id": id } });
+    let body = json!({
+        "sys": { "type": "Array" },
+        "total": 1,
+        "skip": 0,
+        "limit": 100,
+        "items": [{
+            "sys": { "id": "a", "type": "Entry" },
+            "fields": { "name": "A", "next": link("b") }
+        }],
+        "includes": {
+            "Entry": [
+                { "sys": { "id": "b", "type": "Entry" }, "fields": { "name": "B", "next": link("c") } },
+                { "sys": { "id": "c", "type": "Entry" }, "fields": { "name": "C", "next": link("a") } }
+            ]
+        }
+    });
+    Mock::given(method("GET"))
+        .and(path("/spaces/space-id/environments/master/entries"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(body))
+        .mount(&server)
+        .await;
+
+    let contentful_client = client(&server);
+    let actual = contentful_client
+        .get_entries::<Value>(Some(QueryBuilder::new().include(10)))
+        .await
+        .unwrap();
+    assert_eq!(actual[0]["next"]["name"], "B");
+    assert_eq!(actual[0]["next"]["next"]["name"], "C");
+    assert_eq!(actual[0]["next"]["next"]["next"], link("a"));
+
+    let actual = contentful_client
+        .get_entries::<Value>(Some(QueryBuilder::new().include(2)))
+        .await
+        .unwrap();
+    assert_eq!(actual[0]["next"]["next"]["name"], "C");
+    assert_eq!(actual[0]["next"]["next"]["next"], link("a"));
+
+    // The delivery API includes one level of links by default
+    let actual = contentful_client.get_entries::<Value>(None).await.unwrap();
+    assert_eq!(actual[0]["next"]["name"], "B");
+    assert_eq!(actual[0]["next"]["next"], link("c"));
+}
+
+#[tokio::test]
+async fn get_entries_reuses_resolved_entries_unless_circular() {
+    let server = MockServer::start().await;
+    let link = |id: &str| json!({ "sys": { "type": "Link", "linkType": "Entry", "id": id } });
+    let body = json!({
+        "sys": { "type": "Array" },
+        "total": 3,
+        "skip": 0,
+        "limit": 100,
+        "items": [
+            { "sys": { "id": "x", "type": "Entry" }, "fields": { "name": "X", "next": link("b") } },
+            { "sys": { "id": "y", "type": "Entry" }, "fields": { "name": "Y", "next": link("b") } },
+            { "sys": { "id": "a", "type": "Entry" }, "fields": { "name": "A", "next": link("b") } }
+        ],
+        "includes": {
+            "Entry": [
+                { "sys": { "id": "a", "type": "Entry" }, "fields": { "name": "A", "next": link("c") } },
+                { "sys": { "id": "b", "type": "Entry" }, "fields": { "name": "B", "next": link("a") } },
+                { "sys": { "id": "c", "type": "Entry" }, "fields": { "name": "C" } }
+            ]
+        }
+    });
+    Mock::given(method("GET"))
+        .and(path("/spaces/space-id/environments/master/entries"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(body))
+        .mount(&server)
+        .await;
+
+    let contentful_client = client(&server);
+    let actual = contentful_client
+        .get_entries::<Value>(Some(QueryBuilder::new().include(3)))
+        .await
+        .unwrap();
+    assert_eq!(actual[0]["next"]["next"]["next"]["name"], "C");
+    assert_eq!(actual[1]["next"], actual[0]["next"]);
+    // B resolved for X and Y contains A, which links back to the item A
+    assert_eq!(actual[2]["next"]["name"], "B");
+    assert_eq!(actual[2]["next"]["next"], link("a"));
+}
+
+#[tokio::test]
+async fn get_entries_collection_returns_unresolved_links() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/spaces/space-id/environments/master/entries"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "sys": { "type": "Array" },
+            "total": 1,
+            "skip": 0,
+            "limit": 100,
+            "items": [{
+                "sys": { "id": "person-id", "type": "Entry" },
+                "fields": {
+                    "name": "Saju",
+                    "favoriteProduct": {
+                        "sys": { "type": "Link", "linkType": "Entry", "id": "deleted-id" }
+                    },
+                    "image": {
+                        "sys": { "type": "Link", "linkType": "Asset", "id": "missing-id" }
+                    }
+                }
+            }],
+            "errors": [{
+                "sys": { "id": "notResolvable", "type": "error" },
+                "details": { "type": "Link", "linkType": "Entry", "id": "deleted-id" }
+            }]
+        })))
+        .mount(&server)
+        .await;
+
+    let contentful_client = client(&server);
+    let actual = contentful_client
+        .get_entries_collection::<LinkedPerson>(None)
+        .await
+        .unwrap();
+    assert_eq!(actual.errors[0]["sys"]["id"], "notResolvable");
+    assert_eq!(
+        actual.unresolved_links,
+        vec![
+            LinkReference::new("deleted-id", "Entry"),
+            LinkReference::new("missing-id", "Asset")
+        ]
+    );
+    assert_eq!(
+        actual.items[0].favorite_product,
+        LinkReference::new("deleted-id", "Entry")
+    );
+}
+
+#[tokio::test]
+async fn get_entries_keeps_links_that_cannot_be_resolved() {
+    let server = MockServer::start().await;
+    let resource_link = json!({
+        "sys": {
+            "type": "ResourceLink",
+            "linkType": "Contentful:Entry",
+            "urn": "crn:contentful:::content:spaces/other-space/entries/entry-id"
+        }
+    });
+    let tag_link = json!({ "sys": { "type": "Link", "linkType": "Tag", "id": "tag-id" } });
+    let data = json!({ "sys": { "type": "Custom" }, "value": 1 });
+    Mock::given(method("GET"))
+        .and(path("/spaces/space-id/environments/master/entries"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "sys": { "type": "Array" },
+            "total": 1,
+            "skip": 0,
+            "limit": 100,
+            "items": [{
+                "sys": { "id": "person-id", "type": "Entry" },
+                "fields": {
+                    "related": [resource_link],
+                    "tag": tag_link,
+                    "data": data
+                }
+            }]
+        })))
+        .mount(&server)
+        .await;
+
+    let contentful_client = ContentfulClient::builder("token", "space-id")
+        .base_url(&server.uri())
+        .remove_unresolved(true)
+        .build();
+    let actual = contentful_client
+        .get_entries_collection::<Value>(None)
+        .await
+        .unwrap();
+    assert_eq!(actual.items[0]["related"][0], resource_link);
+    assert_eq!(actual.items[0]["tag"], tag_link);
+    assert_eq!(actual.items[0]["data"], data);
+    assert_eq!(
+        actual.unresolved_links,
+        vec![
+            LinkReference::new(
+                "crn:contentful:::content:spaces/other-space/entries/entry-id",
+                "Contentful:Entry"
+            ),
+            LinkReference::new("tag-id", "Tag")
+        ]
+    );
+}
+
+#[tokio::test]
+async fn get_entries_resolves_typed_links_with_sys() {
+    let server = MockServer::start().await;
+    let mut entry = person_entry();
+    entry["fields"]["bestFriend"] =
+        json!({ "sys": { "type": "Link", "linkType": "Entry", "id": "deleted-id" } });
+    Mock::given(method("GET"))
+        .and(path("/spaces/space-id/environments/master/entries"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "sys": { "type": "Array" },
+            "total": 1,
+            "skip": 0,
+            "limit": 100,
+            "items": [entry],
+            "includes": {
+                "Entry": [{
+                    "sys": {
+                        "id": "product-id",
+                        "type": "Entry",
+                        "contentType": { "sys": { "type": "Link", "linkType": "ContentType", "id": "product" } }
+                    },
+                    "fields": { "title": "Contentful" }
+                }],
+                "Asset": [asset()]
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let contentful_client = client(&server);
+    let actual = contentful_client
+        .get_entries::<LinkPerson>(None)
+        .await
+        .unwrap();
+    let person = &actual[0];
+    match &person.favorite_product {
+        Link::Resolved { sys, fields } => {
+            assert_eq!(sys.id, "product-id");
+            assert_eq!(fields.title, "Contentful");
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+    assert_eq!(person.image.id(), "asset-id");
+    assert_eq!(person.image.fields().unwrap().file.file_name, "saju.png");
+    assert_eq!(person.image.sys().unwrap().revision, Some(2));
+    match &person.best_friend {
+        Link::Unresolved { id, link_type } => {
+            assert_eq!(id, "deleted-id");
+            assert_eq!(link_type, "Entry");
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+
+    let actual = serde_json::to_value(&person.favorite_product).unwrap();
+    assert_eq!(actual["title"], "Contentful");
+    assert_eq!(actual["sys"]["id"], "product-id");
+    let actual = serde_json::to_value(&person.best_friend).unwrap();
+    assert_eq!(
+        actual,
+        json!({ "sys": { "type": "Link", "linkType": "Entry", "id": "deleted-id" } })
+    );
+}
+
+#[tokio::test]
+async fn get_entries_keeps_sys_and_metadata_like_get_entry() {
+    let server = MockServer::start().await;
+    let mut entry = person_entry();
+    entry["metadata"] = json!({
+        "tags": [{ "sys": { "type": "Link", "linkType": "Tag", "id": "featured" } }]
+    });
+    Mock::given(method("GET"))
+        .and(path("/spaces/space-id/environments/master/entries"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "sys": { "type": "Array" },
+            "total": 1,
+            "skip": 0,
+            "limit": 100,
+            "items": [entry.clone()]
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(
+            "/spaces/space-id/environments/master/entries/3YrHEsZ9iUsEQOu6IQsI6k",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(entry))
+        .mount(&server)
+        .await;
+
+    let contentful_client = client(&server);
+    let entries = contentful_client
+        .get_entries::<SimplePerson>(None)
+        .await
+        .unwrap();
+    let entry = contentful_client
+        .get_entry::<SimplePerson>("3YrHEsZ9iUsEQOu6IQsI6k")
+        .await
+        .unwrap()
+        .unwrap();
+    for actual in &[&entries[0], &entry] {
+        assert_eq!(actual.sys.id, "3YrHEsZ9iUsEQOu6IQsI6k");
+        assert_eq!(actual.sys.version, Some(1));
+        assert!(actual.sys.updated_at.is_some());
+    }
+
+    let entries = contentful_client.get_entries::<Value>(None).await.unwrap();
+    assert_eq!(entries[0]["metadata"]["tags"][0]["sys"]["id"], "featured");
+    assert_eq!(entries[0]["favoriteProduct"]["sys"]["type"], "Link");
+}
+
+#[tokio::test]
+async fn get_tags_and_entry_metadata_work() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/spaces/space-id/environments/master/tags"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "sys": { "type": "Array" },
+            "total": 1,
+            "skip": 0,
+            "limit": 100,
+            "items": [{
+                "sys": { "id": "featured", "type": "Tag", "version": 1 },
+                "name": "Featured"
+            }]
+        })))
+        .mount(&server)
+        .await;
+    let mut entry = person_entry();
+    entry["metadata"] = json!({
+        "tags": [{ "sys": { "type": "Link", "linkType": "Tag", "id": "featured" } }],
+        "concepts": [{ "sys": { "type": "Link", "linkType": "TaxonomyConcept", "id": "concept-id" } }]
+    });
+    Mock::given(method("GET"))
+        .and(path(
+            "/spaces/space-id/environments/master/entries/3YrHEsZ9iUsEQOu6IQsI6k",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(entry))
+        .mount(&server)
+        .await;
+
+    let contentful_client = client(&server);
+    let actual = contentful_client.get_tags().await.unwrap();
+    assert_eq!(actual[0].name, "Featured");
+    assert_eq!(actual[0].sys.id, "featured");
+
+    let actual = contentful_client
+        .get_contentful_entry("3YrHEsZ9iUsEQOu6IQsI6k")
+        .await
+        .unwrap()
+        .unwrap();
+    let metadata = actual.metadata.unwrap();
+    assert_eq!(metadata.tag_ids().collect::<Vec<_>>(), vec!["featured"]);
+    assert_eq!(metadata.concepts[0].link_type, "TaxonomyConcept");
+
+    let actual = contentful_client
+        .get_entry::<TaggedPerson>("3YrHEsZ9iUsEQOu6IQsI6k")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(actual.metadata.tags[0].id, "featured");
+}
+
+#[tokio::test]
+async fn sync_follows_pages_and_returns_next_sync_token() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/spaces/space-id/environments/master/sync"))
+        .and(query_param("initial", "true"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "sys": { "type": "Array" },
+            "items": [{
+                "sys": { "id": "entry-id", "type": "Entry", "revision": 1 },
+                "fields": { "name": { "en-US": "Saju" } }
+            }],
+            "nextPageUrl": "https://cdn.contentful.com/spaces/space-id/environments/master/sync?sync_token=page-2"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/spaces/space-id/environments/master/sync"))
+        .and(query_param("sync_token", "page-2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "sys": { "type": "Array" },
+            "items": [{
+                "sys": { "id": "asset-id", "type": "Asset" },
+                "fields": { "title": { "en-US": "Saju" } }
+            }],
+            "nextSyncUrl": "https://cdn.contentful.com/spaces/space-id/environments/master/sync?sync_token=delta"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/spaces/space-id/environments/master/sync"))
+        .and(query_param("sync_token", "delta"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "sys": { "type": "Array" },
+            "items": [
+                {
+                    "sys": {
+                        "id": "entry-id",
+                        "type": "DeletedEntry",
+                        "deletedAt": "2024-01-02T03:04:05.000Z"
+                    }
+                },
+                { "sys": { "id": "asset-id", "type": "DeletedAsset" } }
+            ],
+            "nextSyncUrl": "https://cdn.contentful.com/spaces/space-id/environments/master/sync?sync_token=delta-2"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let contentful_client = client(&server);
+    let initial = contentful_client.sync(None).await.unwrap();
+    assert_eq!(initial.items.len(), 2);
+    assert_eq!(
+        initial.entries().next().unwrap().fields["name"]["en-US"],
+        "Saju"
+    );
+    assert_eq!(initial.assets().next().unwrap().sys.id, "asset-id");
+    assert_eq!(initial.next_sync_token, "delta");
+
+    let delta = contentful_client
+        .sync(Some(&initial.next_sync_token))
+        .await
+        .unwrap();
+    let deleted_entry = delta.deleted_entries().next().unwrap();
+    assert_eq!(deleted_entry.id, "entry-id");
+    assert_eq!(
+        deleted_entry.deleted_at.unwrap().to_rfc3339(),
+        "2024-01-02T03:04:05+00:00"
+    );
+    assert_eq!(delta.deleted_assets().next().unwrap().id, "asset-id");
+    assert_eq!(delta.next_sync_token, "delta-2");
+}
+
+#[tokio::test]
+async fn sync_encodes_sync_tokens() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/spaces/space-id/environments/master/sync"))
+        .and(query_param("sync_token", "a+b/c=&d%"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "sys": { "type": "Array" },
+            "items": [],
+            "nextPageUrl": "https://cdn.contentful.com/spaces/space-id/environments/master/sync?sync_token=e%2Bf%3D%26g"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/spaces/space-id/environments/master/sync"))
+        .and(query_param("sync_token", "e+f=&g"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "sys": { "type": "Array" },
+            "items": [],
+            "nextSyncUrl": "https://cdn.contentful.com/spaces/space-id/environments/master/sync?sync_token=h%2Bi"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let contentful_client = client(&server);
+    let actual = contentful_client.sync(Some("a+b/c=&d%")).await.unwrap();
+    assert_eq!(actual.next_sync_token, "h+i");
+}
+
+#[tokio::test]
+async fn get_entries_stream_pages_until_total() {
+    let server = MockServer::start().await;
+    for (skip, names) in [("0", vec!["a", "b"]), ("2", vec!["c"])] {
+        let items = names
+            .iter()
+            .map(|name| json!({ "sys": { "id": name, "type": "Entry" }, "fields": { "name": name } }))
+            .collect::<Vec<_>>();
+        Mock::given(method("GET"))
+            .and(path("/spaces/space-id/environments/master/entries"))
+            .and(query_param("content_type", "person"))
+            .and(query_param("skip", skip))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "sys": { "type": "Array" },
+                "total": 3,
+                "skip": skip.parse::<u32>().unwrap(),
+                "limit": 2,
+                "items": items
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    let contentful_client = client(&server);
+    let query_builder = QueryBuilder::new().content_type_is("person");
+    let actual = contentful_client
+        .get_entries_stream::<Value>(query_builder)
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    let names = actual
+        .iter()
+        .map(|entry| entry["name"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["a", "b", "c"]);
+}
+
+#[tokio::test]
+async fn get_entries_collection_returns_totals() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/spaces/space-id/environments/master/entries"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "sys": { "type": "Array" },
+            "total": 120,
+            "skip": 100,
+            "limit": 100,
+            "items": [{ "sys": { "id": "a", "type": "Entry" }, "fields": { "name": "a" } }]
+        })))
+        .mount(&server)
+        .await;
+
+    let contentful_client = client(&server);
+    let actual = contentful_client
+        .get_entries_collection::<Value>(Some(QueryBuilder::new().skip(100)))
+        .await
+        .unwrap();
+    assert_eq!(actual.total, 120);
+    assert_eq!(actual.skip, 100);
+    assert_eq!(actual.limit, 100);
+    assert_eq!(actual.items[0]["name"], "a");
+}
+
+#[tokio::test]
+async fn get_asset_works() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/spaces/space-id/environments/master/assets/asset-id"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(asset()))
+        .mount(&server)
+        .await;
+
+    let contentful_client = client(&server);
+    let actual = contentful_client
+        .get_asset("asset-id")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(actual.title.as_deref(), Some("Saju"));
+    assert_eq!(actual.file.details.image.unwrap().width, 10);
+    let sys = actual.sys.unwrap();
+    assert_eq!(sys.id, "asset-id");
+    assert_eq!(sys.revision, Some(2));
+}
+
+#[tokio::test]
+async fn get_assets_filters_by_mime_type_group() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/spaces/space-id/environments/master/assets"))
+        .and(query_param("mimetype_group", "image"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "sys": { "type": "Array" },
+            "total": 1,
+            "skip": 0,
+            "limit": 100,
+            "items": [asset()]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let contentful_client = client(&server);
+    let query_builder = QueryBuilder::new().mime_type_group_is("image");
+    let actual = contentful_client
+        .get_assets(Some(query_builder))
+        .await
+        .unwrap();
+    assert_eq!(actual.len(), 1);
+    assert_eq!(actual[0].file.content_type, "image/png");
+    assert_eq!(actual[0].sys.as_ref().unwrap().id, "asset-id");
+}
+
+#[tokio::test]
+async fn get_content_types_works() {
+    let server = MockServer::start().await;
+    let content_type = json!({
+        "sys": { "id": "person", "type": "ContentType", "revision": 3 },
+        "name": "Person",
+        "displayField": "name",
+        "fields": [
+            {
+                "id": "name",
+                "name": "Name",
+                "type": "Symbol",
+                "localized": true,
+                "required": true,
+                "validations": [{ "unique": true }],
+                "disabled": false,
+                "omitted": false
+            },
+            {
+                "id": "favoriteProduct",
+                "name": "Favorite product",
+                "type": "Link",
+                "linkType": "Entry",
+                "localized": false,
+                "required": false
+            },
+            {
+                "id": "interestedProducts",
+                "name": "Interested products",
+                "type": "Array",
+                "items": {
+                    "type": "Link",
+                    "linkType": "Entry",
+                    "validations": [{ "linkContentType": ["product"] }]
+                }
+            }
+        ]
+    });
+    Mock::given(method("GET"))
+        .and(path(
+            "/spaces/space-id/environments/master/content_types/person",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(content_type.clone()))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/spaces/space-id/environments/master/content_types"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "sys": { "type": "Array" },
+            "total": 1,
+            "skip": 0,
+            "limit": 100,
+            "items": [content_type]
+        })))
+        .mount(&server)
+        .await;
+
+    let contentful_client = client(&server);
+    let actual = contentful_client
+        .get_content_type("person")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(actual.sys.id, "person");
+    assert_eq!(actual.display_field.as_deref(), Some("name"));
+    assert_eq!(actual.fields[0].field_type, "Symbol");
+    assert!(actual.fields[0].required);
+    assert_eq!(actual.fields[0].validations[0]["unique"], true);
+    assert_eq!(actual.fields[1].link_type.as_deref(), Some("Entry"));
+    let items = actual.fields[2].items.as_ref().unwrap();
+    assert_eq!(items.item_type, "Link");
+    assert_eq!(items.link_type.as_deref(), Some("Entry"));
+
+    let actual = contentful_client.get_content_types(None).await.unwrap();
+    assert_eq!(actual.len(), 1);
+    assert_eq!(actual[0].name, "Person");
+}
+
+#[tokio::test]
+async fn get_space_and_locales_works() {
+    let server = MockServer::start().await;
+    let locales = json!([
+        {
+            "sys": { "id": "locale-1", "type": "Locale" },
+            "code": "en-US",
+            "name": "English (United States)",
+            "default": true,
+            "fallbackCode": null
+        },
+        {
+            "sys": { "id": "locale-2", "type": "Locale" },
+            "code": "de-DE",
+            "name": "German (Germany)",
+            "default": false,
+            "fallbackCode": "en-US"
+        }
+    ]);
+    Mock::given(method("GET"))
+        .and(path("/spaces/space-id"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "sys": { "id": "space-id", "type": "Space" },
+            "name": "Saju",
+            "locales": locales.clone()
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/spaces/space-id/environments/master/locales"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "sys": { "type": "Array" },
+            "total": 2,
+            "skip": 0,
+            "limit": 1000,
+            "items": locales
+        })))
+        .mount(&server)
+        .await;
+
+    let contentful_client = client(&server);
+    let space = contentful_client.get_space().await.unwrap().unwrap();
+    assert_eq!(space.name, "Saju");
+    assert_eq!(space.system_properties.id, "space-id");
+
+    let locales = contentful_client.get_locales().await.unwrap();
+    let default_locale = locales.iter().find(|locale| locale.default).unwrap();
+    assert_eq!(default_locale.code, "en-US");
+    assert_eq!(locales[1].fallback_code.as_deref(), Some("en-US"));
+}
+
+fn client(server: &MockServer) -> ContentfulClient {
+    let _ = env_logger::builder().is_test(true).try_init();
+    ContentfulClient::builder("token", "space-id")
+        .base_url(&server.uri())
+        .build()
+}
+
+fn person_entry() -> Value {
+    json!({
+        "sys": {
+            "id": "3YrHEsZ9iUsEQOu6IQsI6k",
+            "type": "Entry",
+            "version": 1,
+            "createdAt": "2021-01-01T00:00:00.000Z",
+            "updatedAt": "2021-01-02T00:00:00.000Z"
+        },
+        "fields": {
+            "name": "Saju",
+            "title": "Mr",
+            "favoriteProduct": {
+                "sys": { "type": "Link", "linkType": "Entry", "id": "product-id" }
+            },
+            "image": {
+                "sys": { "type": "Link", "linkType": "Asset", "id": "asset-id" }
+            }
+        }
+    })
+}
+
+fn asset() -> Value {
+    json!({
+        "sys": { "id": "asset-id", "type": "Asset", "revision": 2 },
+        "fields": {
+            "title": "Saju",
+            "file": {
+                "fileName": "saju.png",
+                "contentType": "image/png",
+                "url": "//images.ctfassets.net/saju.png",
+                "details": { "size": 1024, "image": { "width": 10, "height": 20 } }
+            }
+        }
+    })
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SimplePerson {
+    name: String,
+    title: String,
+    sys: SystemProperties,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Person {
+    name: String,
+    title: String,
+    favorite_product: Option<Product>,
+    image: Option<Asset>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Product {
+    title: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Article {
+    title: String,
+    body: Document,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LinkedPerson {
+    name: String,
+    favorite_product: LinkReference,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LinkPerson {
+    name: String,
+    favorite_product: Link<Product>,
+    image: Link<Asset>,
+    best_friend: Link<Person>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct TaggedPerson {
+    name: String,
+    metadata: Metadata,
+}
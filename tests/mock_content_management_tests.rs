@@ -0,0 +1,643 @@
+use contentful::{
+    models::{
+        AssetFields, AssetFile, ContentType, ContentTypeField, Entry, LinkReference, Metadata,
+        SystemProperties,
+    },
+    ContentfulManagementClient, Error,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::time::Duration;
+use wiremock::matchers::{body_bytes, body_json, body_partial_json, header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn get_entry_for_locale_works() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(
+            "/spaces/space-id/environments/master/entries/entry-id",
+        ))
+        .and(header("authorization", "Bearer token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "sys": { "id": "entry-id", "version": 7 },
+            "fields": {
+                "name": { "en-US": "Saju" },
+                "title": { "en-US": "Mr" }
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let contentful_client = client(&server);
+    let actual = contentful_client
+        .get_entry_for_locale::<Person>("entry-id", "en-US")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(actual.fields.name, "Saju");
+    assert_eq!(actual.sys.version, Some(7));
+}
+
+#[tokio::test]
+async fn create_entry_for_locale_works() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/spaces/space-id/environments/master/entries"))
+        .and(header("X-Contentful-Content-Type", "person"))
+        .and(body_json(json!({
+            "fields": {
+                "name": { "en-US": "Saju-rs" },
+                "title": { "en-US": "Mr" }
+            }
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "sys": { "id": "new-entry-id", "version": 1 },
+            "fields": {
+                "name": { "en-US": "Saju-rs" },
+                "title": { "en-US": "Mr" }
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let contentful_client = client(&server);
+    let person = Person {
+        name: "Saju-rs".into(),
+        title: "Mr".into(),
+    };
+    let actual = contentful_client
+        .create_entry_for_locale::<Person>(&person, "person", "en-US")
+        .await
+        .unwrap();
+    assert_eq!(actual.name, "Saju-rs");
+}
+
+#[tokio::test]
+async fn create_entry_for_locale_rejects_entries_that_are_not_objects() {
+    let server = MockServer::start().await;
+
+    let contentful_client = client(&server);
+    let actual = contentful_client
+        .create_entry_for_locale(&"Saju".to_string(), "person", "en-US")
+        .await;
+    assert!(matches!(actual, Err(Error::InvalidInput(_))));
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn create_or_update_entry_for_locale_sends_version() {
+    let server = MockServer::start().await;
+    Mock::given(method("PUT"))
+        .and(path(
+            "/spaces/space-id/environments/master/entries/entry-id",
+        ))
+        .and(header("X-Contentful-Version", "13"))
+        .and(header("X-Contentful-Content-Type", "person"))
+        .and(body_partial_json(json!({
+            "metadata": {
+                "tags": [{ "sys": { "type": "Link", "linkType": "Tag", "id": "featured" } }]
+            }
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "sys": { "id": "entry-id", "version": 14 },
+            "fields": {
+                "name": { "en-US": "Saju-rs-3" },
+                "title": { "en-US": "Mr" }
+            },
+            "metadata": {
+                "tags": [{ "sys": { "type": "Link", "linkType": "Tag", "id": "featured" } }]
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let contentful_client = client(&server);
+    let person = Person {
+        name: "Saju-rs-3".into(),
+        title: "Mr".into(),
+    };
+    let sys = SystemProperties::with_version("entry-id".into(), 13);
+    let mut entry = Entry::new(person, sys);
+    entry.metadata = Some(Metadata {
+        tags: vec![LinkReference::new("featured", "Tag")],
+        ..Default::default()
+    });
+    let actual = contentful_client
+        .create_or_update_entry_for_locale(&entry, "entry-id", "en-US", "person")
+        .await
+        .unwrap();
+    assert_eq!(actual.fields.name, "Saju-rs-3");
+    assert_eq!(actual.sys.version, Some(14));
+    assert_eq!(actual.metadata, entry.metadata);
+}
+
+#[tokio::test]
+async fn create_or_update_entry_returns_version_conflict() {
+    let server = MockServer::start().await;
+    Mock::given(method("PUT"))
+        .respond_with(ResponseTemplate::new(409).set_body_json(json!({
+            "sys": { "type": "Error", "id": "VersionMismatch" },
+            "message": "Version mismatch error."
+        })))
+        .mount(&server)
+        .await;
+
+    let contentful_client = client(&server);
+    let sys = SystemProperties::with_version("entry-id".into(), 1);
+    let entry = Entry::new(json!({ "name": { "en-US": "Saju" } }), sys);
+    let actual = contentful_client
+        .create_or_update_entry(&entry, "entry-id", "person")
+        .await;
+    assert!(matches!(actual, Err(Error::VersionConflict(_))));
+}
+
+#[tokio::test]
+async fn create_entry_returns_validation_errors() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(422).set_body_json(json!({
+            "sys": { "type": "Error", "id": "ValidationFailed" },
+            "message": "Validation error",
+            "details": {
+                "errors": [{ "name": "required", "path": ["fields", "name"] }]
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let contentful_client = client(&server);
+    let actual = contentful_client
+        .create_entry_from_json::<Value>(&json!({ "fields": {} }), "person")
+        .await;
+    match actual {
+        Err(Error::Validation { error, errors }) => {
+            assert_eq!(error.id.as_deref(), Some("ValidationFailed"));
+            assert_eq!(errors[0]["name"], "required");
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn publish_and_unpublish_entry_send_version() {
+    let server = MockServer::start().await;
+    Mock::given(method("PUT"))
+        .and(path(
+            "/spaces/space-id/environments/master/entries/entry-id/published",
+        ))
+        .and(header("X-Contentful-Version", "3"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "sys": { "id": "entry-id", "version": 4, "publishedVersion": 3 },
+            "fields": { "name": { "en-US": "Saju" } }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path(
+            "/spaces/space-id/environments/master/entries/entry-id/published",
+        ))
+        .and(header("X-Contentful-Version", "4"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "sys": { "id": "entry-id", "version": 5 },
+            "fields": { "name": { "en-US": "Saju" } }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let contentful_client = client(&server);
+    let published = contentful_client
+        .publish_entry("entry-id", 3)
+        .await
+        .unwrap();
+    assert!(published.sys.is_published());
+    let unpublished = contentful_client
+        .unpublish_entry("entry-id", published.sys.version.unwrap())
+        .await
+        .unwrap();
+    assert!(unpublished.sys.is_draft());
+    assert_eq!(unpublished.sys.version, Some(5));
+}
+
+#[tokio::test]
+async fn archive_and_unarchive_entry_send_version() {
+    let server = MockServer::start().await;
+    Mock::given(method("PUT"))
+        .and(path(
+            "/spaces/space-id/environments/master/entries/entry-id/archived",
+        ))
+        .and(header("X-Contentful-Version", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "sys": { "id": "entry-id", "version": 3, "archivedVersion": 2 },
+            "fields": {}
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path(
+            "/spaces/space-id/environments/master/entries/entry-id/archived",
+        ))
+        .and(header("X-Contentful-Version", "3"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "sys": { "id": "entry-id", "version": 4 },
+            "fields": {}
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let contentful_client = client(&server);
+    let archived = contentful_client
+        .archive_entry("entry-id", 2)
+        .await
+        .unwrap();
+    assert!(archived.sys.is_archived());
+    let unarchived = contentful_client
+        .unarchive_entry("entry-id", 3)
+        .await
+        .unwrap();
+    assert!(!unarchived.sys.is_archived());
+}
+
+#[tokio::test]
+async fn delete_entry_sends_version() {
+    let server = MockServer::start().await;
+    Mock::given(method("DELETE"))
+        .and(path(
+            "/spaces/space-id/environments/master/entries/entry-id",
+        ))
+        .and(header("X-Contentful-Version", "5"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let contentful_client = client(&server);
+    contentful_client.delete_entry("entry-id", 5).await.unwrap();
+}
+
+#[tokio::test]
+async fn upload_process_and_publish_asset() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/spaces/space-id/uploads"))
+        .and(header("content-type", "application/octet-stream"))
+        .and(body_bytes(b"image bytes".to_vec()))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "sys": { "id": "upload-id", "type": "Upload" }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let unprocessed_file = json!({
+        "fileName": "saju.png",
+        "contentType": "image/png",
+        "uploadFrom": { "sys": { "type": "Link", "linkType": "Upload", "id": "upload-id" } }
+    });
+    Mock::given(method("POST"))
+        .and(path("/spaces/space-id/environments/master/assets"))
+        .and(body_json(json!({
+            "fields": {
+                "title": { "en-US": "Saju" },
+                "file": { "en-US": unprocessed_file }
+            }
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "sys": { "id": "asset-id", "type": "Asset", "version": 1 },
+            "fields": {
+                "title": { "en-US": "Saju" },
+                "file": { "en-US": unprocessed_file }
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path(
+            "/spaces/space-id/environments/master/assets/asset-id/files/en-US/process",
+        ))
+        .and(header("X-Contentful-Version", "1"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/spaces/space-id/environments/master/assets/asset-id"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "sys": { "id": "asset-id", "type": "Asset", "version": 1 },
+            "fields": { "file": { "en-US": unprocessed_file } }
+        })))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/spaces/space-id/environments/master/assets/asset-id"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "sys": { "id": "asset-id", "type": "Asset", "version": 2 },
+            "fields": {
+                "file": {
+                    "en-US": {
+                        "fileName": "saju.png",
+                        "contentType": "image/png",
+                        "url": "//images.ctfassets.net/saju.png",
+                        "details": { "size": 11, "image": { "width": 1, "height": 1 } }
+                    }
+                }
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path(
+            "/spaces/space-id/environments/master/assets/asset-id/published",
+        ))
+        .and(header("X-Contentful-Version", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "sys": { "id": "asset-id", "type": "Asset", "version": 3, "publishedVersion": 2 },
+            "fields": {}
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let contentful_client = client(&server);
+    let upload = contentful_client
+        .upload(b"image bytes".to_vec())
+        .await
+        .unwrap();
+    let mut fields = AssetFields::default();
+    fields.title.insert("en-US".into(), "Saju".into());
+    fields.file.insert(
+        "en-US".into(),
+        AssetFile::from_upload("saju.png", "image/png", &upload.sys.id),
+    );
+    let asset = contentful_client.create_asset(&fields).await.unwrap();
+    assert!(!asset.fields.file["en-US"].is_processed());
+    contentful_client
+        .process_asset_file(&asset.sys.id, "en-US", asset.sys.version.unwrap())
+        .await
+        .unwrap();
+    let processed = contentful_client
+        .wait_for_asset_file(&asset.sys.id, "en-US", Duration::from_secs(5))
+        .await
+        .unwrap();
+    let file = &processed.fields.file["en-US"];
+    assert_eq!(file.url.as_deref(), Some("//images.ctfassets.net/saju.png"));
+    assert_eq!(file.details.as_ref().unwrap().size, 11);
+    let published = contentful_client
+        .publish_asset(&asset.sys.id, processed.sys.version.unwrap())
+        .await
+        .unwrap();
+    assert!(published.sys.is_published());
+}
+
+#[tokio::test]
+async fn wait_for_asset_file_times_out() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/spaces/space-id/environments/master/assets/asset-id"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "sys": { "id": "asset-id", "type": "Asset", "version": 2 },
+            "fields": {
+                "file": { "en-US": { "fileName": "saju.png", "contentType": "image/png" } }
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let contentful_client = client(&server);
+    let actual = contentful_client
+        .wait_for_asset_file("asset-id", "en-US", Duration::from_millis(100))
+        .await;
+    assert!(matches!(actual, Err(Error::AssetNotProcessed { .. })));
+}
+
+#[tokio::test]
+async fn create_and_activate_content_type() {
+    let server = MockServer::start().await;
+    let content_type_json = json!({
+        "name": "Person",
+        "displayField": "name",
+        "fields": [{
+            "id": "name",
+            "name": "Name",
+            "type": "Symbol",
+            "localized": false,
+            "required": true,
+            "validations": [],
+            "disabled": false,
+            "omitted": false
+        }]
+    });
+    Mock::given(method("PUT"))
+        .and(path(
+            "/spaces/space-id/environments/master/content_types/person",
+        ))
+        .and(body_json(&content_type_json))
+        .respond_with(ResponseTemplate::new(201).set_body_json({
+            let mut created = content_type_json.clone();
+            created["sys"] = json!({ "id": "person", "type": "ContentType", "version": 1 });
+            created
+        }))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path(
+            "/spaces/space-id/environments/master/content_types/person/published",
+        ))
+        .and(header("X-Contentful-Version", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json({
+            let mut activated = content_type_json.clone();
+            activated["sys"] = json!({
+                "id": "person",
+                "type": "ContentType",
+                "version": 2,
+                "publishedVersion": 1
+            });
+            activated
+        }))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let contentful_client = client(&server);
+    let mut name = ContentTypeField::new("name", "Name", "Symbol");
+    name.required = true;
+    let mut content_type = ContentType::new("person", "Person", vec![name]);
+    content_type.display_field = Some("name".into());
+    let created = contentful_client
+        .create_or_update_content_type(&content_type)
+        .await
+        .unwrap();
+    assert_eq!(created.sys.version, Some(1));
+    let activated = contentful_client
+        .activate_content_type("person", 1)
+        .await
+        .unwrap();
+    assert!(activated.sys.is_published());
+    assert_eq!(activated.fields[0].id, "name");
+}
+
+#[tokio::test]
+async fn update_deactivate_and_delete_content_type_send_version() {
+    let server = MockServer::start().await;
+    let content_type_json = |version: i32| {
+        json!({
+            "sys": { "id": "person", "type": "ContentType", "version": version },
+            "name": "Person",
+            "fields": []
+        })
+    };
+    Mock::given(method("PUT"))
+        .and(path(
+            "/spaces/space-id/environments/master/content_types/person",
+        ))
+        .and(header("X-Contentful-Version", "4"))
+        .and(body_json(json!({ "name": "Person", "fields": [] })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(content_type_json(5)))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path(
+            "/spaces/space-id/environments/master/content_types/person/published",
+        ))
+        .and(header("X-Contentful-Version", "5"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(content_type_json(6)))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path(
+            "/spaces/space-id/environments/master/content_types/person",
+        ))
+        .and(header("X-Contentful-Version", "6"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let contentful_client = client(&server);
+    let mut content_type = ContentType::new("person", "Person", Vec::new());
+    content_type.sys.version = Some(4);
+    let updated = contentful_client
+        .create_or_update_content_type(&content_type)
+        .await
+        .unwrap();
+    let deactivated = contentful_client
+        .deactivate_content_type("person", updated.sys.version.unwrap())
+        .await
+        .unwrap();
+    assert!(deactivated.sys.is_draft());
+    contentful_client
+        .delete_content_type("person", deactivated.sys.version.unwrap())
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn update_content_type_keeps_all_properties() {
+    let server = MockServer::start().await;
+    let body = json!({
+        "name": "Person",
+        "displayField": "name",
+        "fields": [
+            {
+                "id": "name",
+                "name": "Name",
+                "type": "Symbol",
+                "localized": false,
+                "required": true,
+                "validations": [],
+                "disabled": false,
+                "omitted": false,
+                "defaultValue": { "en-US": "Saju" }
+            },
+            {
+                "id": "old",
+                "name": "Old",
+                "type": "Symbol",
+                "localized": false,
+                "required": false,
+                "validations": [],
+                "disabled": false,
+                "omitted": true,
+                "deleted": true
+            },
+            {
+                "id": "related",
+                "name": "Related",
+                "type": "Array",
+                "items": {
+                    "type": "ResourceLink",
+                    "validations": [],
+                    "allowedResources": [{ "type": "Contentful:Entry", "source": "crn:contentful:::content:spaces/other-space" }]
+                },
+                "localized": false,
+                "required": false,
+                "validations": [],
+                "disabled": false,
+                "omitted": false
+            }
+        ],
+        "metadata": { "taxonomy": [] }
+    });
+    let mut content_type_json = body.clone();
+    content_type_json["sys"] = json!({ "id": "person", "type": "ContentType", "version": 4 });
+    Mock::given(method("GET"))
+        .and(path(
+            "/spaces/space-id/environments/master/content_types/person",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&content_type_json))
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path(
+            "/spaces/space-id/environments/master/content_types/person",
+        ))
+        .and(header("X-Contentful-Version", "4"))
+        .and(body_json(&body))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&content_type_json))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let contentful_client = client(&server);
+    let content_type = contentful_client
+        .get_content_type("person")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        content_type.fields[0].default_value,
+        Some(json!({ "en-US": "Saju" }))
+    );
+    assert!(content_type.fields[1].deleted);
+    contentful_client
+        .create_or_update_content_type(&content_type)
+        .await
+        .unwrap();
+}
+
+fn client(server: &MockServer) -> ContentfulManagementClient {
+    let _ = env_logger::builder().is_test(true).try_init();
+    ContentfulManagementClient::builder("token", "space-id")
+        .base_url(&server.uri())
+        .upload_base_url(&server.uri())
+        .build()
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Person {
+    pub name: String,
+    pub title: String,
+}
@@ -0,0 +1,94 @@
+use contentful::QueryBuilder;
+
+#[test]
+fn build_without_parameters_is_empty() {
+    assert_eq!(QueryBuilder::new().build(), "");
+}
+
+#[test]
+fn build_keeps_parameter_order() {
+    let query_string = QueryBuilder::new()
+        .content_type_is("product")
+        .field_equals("fields.name", "Saju")
+        .order_by("-sys.createdAt")
+        .limit(10)
+        .skip(20)
+        .include(2)
+        .locale_is("en-US")
+        .build();
+    assert_eq!(
+        query_string,
+        "?content_type=product&fields.name=Saju&order=-sys.createdAt&limit=10&skip=20&include=2&locale=en-US"
+    );
+}
+
+#[test]
+fn limit_does_not_clobber_order_by() {
+    let query_string = QueryBuilder::new().order_by("fields.name").limit(5).build();
+    assert_eq!(query_string, "?order=fields.name&limit=5");
+}
+
+#[test]
+fn setting_a_parameter_again_replaces_its_value() {
+    let query_string = QueryBuilder::new()
+        .skip(0)
+        .content_type_is("product")
+        .skip(100)
+        .build();
+    assert_eq!(query_string, "?skip=100&content_type=product");
+}
+
+#[test]
+fn add_query_param_allows_repeated_keys() {
+    let query_string = QueryBuilder::new()
+        .add_query_param("fields.tags[ne]", "a")
+        .add_query_param("fields.tags[ne]", "b")
+        .build();
+    assert_eq!(query_string, "?fields.tags[ne]=a&fields.tags[ne]=b");
+}
+
+#[test]
+fn build_percent_encodes_values() {
+    let query_string = QueryBuilder::new()
+        .field_matches("fields.title", "salt & pepper")
+        .field_greater_than("sys.createdAt", "2021-01-01T00:00:00Z")
+        .field_includes("fields.tags", "a,b+c")
+        .build();
+    assert_eq!(
+        query_string,
+        "?fields.title[match]=salt%20%26%20pepper&sys.createdAt[gt]=2021-01-01T00%3A00%3A00Z&fields.tags[in]=a,b%2Bc"
+    );
+}
+
+#[test]
+fn field_restrictions_work() {
+    let query_string = QueryBuilder::new()
+        .field_does_not_equal("fields.a", "1")
+        .field_equals_all("fields.b", "2,3")
+        .field_excludes("fields.c", "4")
+        .field_exists("fields.d", true)
+        .field_less_than("fields.e", "5")
+        .field_less_than_or_equal_to("fields.f", "6")
+        .field_greater_than_or_equal_to("fields.g", "7")
+        .links_to_entry("entry-id")
+        .links_to_asset("asset-id")
+        .select_fields("fields.a,fields.b")
+        .build();
+    assert_eq!(
+        query_string,
+        "?fields.a[ne]=1&fields.b[all]=2,3&fields.c[nin]=4&fields.d[exists]=true&fields.e[lt]=5&fields.f[lte]=6&fields.g[gte]=7&links_to_entry=entry-id&links_to_asset=asset-id&select=fields.a,fields.b"
+    );
+}
+
+#[test]
+fn tag_filters_work() {
+    let query_string = QueryBuilder::new()
+        .tags_include("a,b")
+        .tags_include_all("c,d")
+        .tags_exist(true)
+        .build();
+    assert_eq!(
+        query_string,
+        "?metadata.tags.sys.id[in]=a,b&metadata.tags.sys.id[all]=c,d&metadata.tags[exists]=true"
+    );
+}
@@ -0,0 +1,169 @@
+use contentful::{ContentfulClient, ContentfulManagementClient, Error, RetryPolicy};
+use reqwest::{Method, StatusCode};
+use serde_json::json;
+use std::time::Duration;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[test]
+fn delay_for_backs_off_exponentially() {
+    let retry_policy = RetryPolicy::new()
+        .initial_backoff(Duration::from_millis(100))
+        .max_backoff(Duration::from_secs(1))
+        .jitter(false);
+    assert_eq!(retry_policy.delay_for(0, None), Duration::from_millis(100));
+    assert_eq!(retry_policy.delay_for(1, None), Duration::from_millis(200));
+    assert_eq!(retry_policy.delay_for(2, None), Duration::from_millis(400));
+    assert_eq!(retry_policy.delay_for(4, None), Duration::from_secs(1));
+    assert_eq!(retry_policy.delay_for(40, None), Duration::from_secs(1));
+}
+
+#[test]
+fn delay_for_with_jitter_stays_within_half_and_full_backoff() {
+    let retry_policy = RetryPolicy::new().initial_backoff(Duration::from_millis(100));
+    for _ in 0..100 {
+        let delay = retry_policy.delay_for(1, None);
+        assert!(delay >= Duration::from_millis(100));
+        assert!(delay <= Duration::from_millis(200));
+    }
+}
+
+#[test]
+fn delay_for_respects_rate_limit_reset() {
+    let retry_policy = RetryPolicy::new();
+    assert_eq!(retry_policy.delay_for(0, Some(3)), Duration::from_secs(3));
+
+    let retry_policy = RetryPolicy::new()
+        .initial_backoff(Duration::from_millis(100))
+        .jitter(false)
+        .respect_rate_limit_reset(false);
+    assert_eq!(retry_policy.delay_for(0, Some(3)), Duration::from_millis(100));
+}
+
+#[test]
+fn should_retry_works() {
+    let retry_policy = RetryPolicy::new();
+    assert!(retry_policy.should_retry(&Method::GET, StatusCode::TOO_MANY_REQUESTS));
+    assert!(retry_policy.should_retry(&Method::GET, StatusCode::SERVICE_UNAVAILABLE));
+    assert!(retry_policy.should_retry(&Method::PUT, StatusCode::BAD_GATEWAY));
+    assert!(!retry_policy.should_retry(&Method::GET, StatusCode::NOT_FOUND));
+
+    let retry_policy = RetryPolicy::new().retry_on_server_errors(false);
+    assert!(retry_policy.should_retry(&Method::GET, StatusCode::TOO_MANY_REQUESTS));
+    assert!(!retry_policy.should_retry(&Method::GET, StatusCode::BAD_GATEWAY));
+}
+
+#[test]
+fn should_retry_non_idempotent_requests_only_when_rate_limited() {
+    let retry_policy = RetryPolicy::new();
+    assert!(retry_policy.should_retry(&Method::POST, StatusCode::TOO_MANY_REQUESTS));
+    assert!(!retry_policy.should_retry(&Method::POST, StatusCode::SERVICE_UNAVAILABLE));
+
+    let retry_policy = RetryPolicy::new().retry_non_idempotent_on_server_errors(true);
+    assert!(retry_policy.should_retry(&Method::POST, StatusCode::SERVICE_UNAVAILABLE));
+}
+
+#[tokio::test]
+async fn builder_retry_policy_retries_rate_limited_requests() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(
+            "/spaces/space-id/environments/master/entries/entry-id",
+        ))
+        .respond_with(ResponseTemplate::new(429).insert_header("X-Contentful-RateLimit-Reset", "0"))
+        .up_to_n_times(2)
+        .expect(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(
+            "/spaces/space-id/environments/master/entries/entry-id",
+        ))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!({ "sys": { "id": "entry-id" } })),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let contentful_client = ContentfulClient::builder("token", "space-id")
+        .base_url(&server.uri())
+        .retry_policy(RetryPolicy::new().max_retries(2))
+        .build();
+    let actual = contentful_client
+        .get_entry_json_value("entry-id")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(actual["sys"]["id"], "entry-id");
+}
+
+#[tokio::test]
+async fn builder_retry_policy_gives_up_after_max_retries() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(503))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let contentful_client = ContentfulClient::builder("token", "space-id")
+        .base_url(&server.uri())
+        .retry_policy(
+            RetryPolicy::new()
+                .max_retries(1)
+                .initial_backoff(Duration::from_millis(1))
+                .jitter(false),
+        )
+        .build();
+    let actual = contentful_client.get_entry_json_value("entry-id").await;
+    assert!(matches!(actual, Err(Error::Api(error)) if error.status == 503));
+}
+
+#[tokio::test]
+async fn retry_policy_does_not_retry_failed_posts() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/spaces/space-id/environments/master/entries"))
+        .respond_with(ResponseTemplate::new(502))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let contentful_client = ContentfulManagementClient::builder("token", "space-id")
+        .base_url(&server.uri())
+        .retry_policy(RetryPolicy::new().initial_backoff(Duration::from_millis(1)))
+        .build();
+    let actual = contentful_client
+        .create_entry_from_json::<serde_json::Value>(&json!({ "fields": {} }), "person")
+        .await;
+    assert!(matches!(actual, Err(Error::Api(error)) if error.status == 502));
+}
+
+#[tokio::test]
+async fn retry_policy_retries_rate_limited_posts() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(429).insert_header("X-Contentful-RateLimit-Reset", "0"))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "sys": { "id": "entry-id" },
+            "fields": { "name": "Saju" }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let contentful_client = ContentfulManagementClient::builder("token", "space-id")
+        .base_url(&server.uri())
+        .build();
+    let actual = contentful_client
+        .create_entry_from_json::<serde_json::Value>(&json!({ "fields": {} }), "person")
+        .await
+        .unwrap();
+    assert_eq!(actual["name"], "Saju");
+}
@@ -0,0 +1,454 @@
+use contentful::models::rich_text::{Document, MarkType, Node};
+use contentful::renderers::{HtmlRenderer, MarkdownRenderer, PlainTextRenderer};
+use serde_json::{json, Value};
+
+#[test]
+fn document_round_trips() {
+    let json = rich_text();
+    let document = serde_json::from_value::<Document>(json.clone()).unwrap();
+    let actual = serde_json::to_value(&document).unwrap();
+    assert_eq!(actual, json);
+}
+
+#[test]
+fn document_deserializes_to_typed_nodes() {
+    let document = serde_json::from_value::<Document>(rich_text()).unwrap();
+    assert_eq!(document.content.len(), 6);
+    match &document.content[0] {
+        Node::Heading1(heading) => match &heading.content[0] {
+            Node::Text(text) => {
+                assert_eq!(text.value, "Title");
+                assert!(text.has_mark(&MarkType::Bold));
+                assert_eq!(text.marks[1].mark_type, MarkType::Other("highlight".into()));
+            }
+            other => panic!("unexpected {:?}", other),
+        },
+        other => panic!("unexpected {:?}", other),
+    }
+    match &document.content[1].content()[1] {
+        Node::Hyperlink(hyperlink) => assert_eq!(hyperlink.data.uri, "https://example.com"),
+        other => panic!("unexpected {:?}", other),
+    }
+    match &document.content[3] {
+        Node::EmbeddedEntryBlock(embedded) => {
+            assert_eq!(embedded.data.target["sys"]["id"], "entry-id")
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+    assert_eq!(document.content[5].node_type(), "table");
+}
+
+#[test]
+fn document_keeps_resource_and_unknown_nodes() {
+    let resource_link = json!({
+        "sys": {
+            "type": "ResourceLink",
+            "linkType": "Contentful:Entry",
+            "urn": "crn:contentful:::content:spaces/other-space/entries/entry-id"
+        }
+    });
+    let json = json!({
+        "nodeType": "document",
+        "data": {},
+        "content": [
+            { "nodeType": "embedded-resource-block", "data": { "target": resource_link }, "content": [] },
+            paragraph(vec![
+                json!({ "nodeType": "embedded-resource-inline", "data": { "target": resource_link }, "content": [] }),
+                json!({ "nodeType": "resource-hyperlink", "data": { "target": resource_link }, "content": [text("other space", &[])] }),
+            ]),
+            { "nodeType": "future-node", "data": { "key": "value" }, "content": [paragraph(vec![text("Inside", &[])])] }
+        ]
+    });
+    let document = serde_json::from_value::<Document>(json.clone()).unwrap();
+    assert!(matches!(
+        document.content[0],
+        Node::EmbeddedResourceBlock(_)
+    ));
+    match &document.content[2] {
+        Node::Unknown(unknown) => {
+            assert_eq!(unknown.node_type, "future-node");
+            assert_eq!(unknown.data["key"], "value");
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+    assert_eq!(document.content[2].node_type(), "future-node");
+    assert_eq!(serde_json::to_value(&document).unwrap(), json);
+
+    let html = HtmlRenderer::new()
+        .embedded_entry_handler(|node, target| {
+            format!("[{} {}]", node.node_type(), target["sys"]["type"])
+        })
+        .render(&document);
+    assert_eq!(
+        html,
+        "[embedded-resource-block \"ResourceLink\"]\
+         <p>[embedded-resource-inline \"ResourceLink\"]other space</p>\
+         <p>Inside</p>"
+    );
+    assert_eq!(
+        PlainTextRenderer::new().render(&document),
+        "other space\n\nInside"
+    );
+    assert_eq!(
+        MarkdownRenderer::new().render(&document),
+        "other space\n\nInside"
+    );
+}
+
+#[test]
+fn document_requires_document_root() {
+    let actual = serde_json::from_value::<Document>(json!({
+        "nodeType": "paragraph",
+        "data": {},
+        "content": []
+    }));
+    assert!(actual.is_err());
+}
+
+#[test]
+fn html_renderer_renders_escaped_html() {
+    let document = serde_json::from_value::<Document>(rich_text()).unwrap();
+    let includes = json!({
+        "Asset": [{
+            "sys": { "id": "asset-id", "type": "Asset" },
+            "fields": { "title": "Quote", "file": { "url": "//images.ctfassets.net/a.png", "contentType": "image/png" } }
+        }]
+    });
+    let actual = HtmlRenderer::new().includes(&includes).render(&document);
+    assert_eq!(
+        actual,
+        "<h1><b>Title</b></h1>\
+        <p>See <a href=\"https://example.com\"><i>example</i></a></p>\
+        <ul><li><p><code>Item</code></p></li></ul>\
+        <blockquote><p><a href=\"//images.ctfassets.net/a.png\">Quote</a></p></blockquote>\
+        <table><tr><th><p>A &amp; &lt;B&gt;</p></th><td><p>1</p></td></tr></table>"
+    );
+}
+
+#[test]
+fn html_renderer_uses_handlers() {
+    let document = serde_json::from_value::<Document>(rich_text()).unwrap();
+    let includes = json!({
+        "Entry": [{
+            "sys": { "id": "entry-id", "type": "Entry" },
+            "fields": { "name": "Saju" }
+        }]
+    });
+    let actual = HtmlRenderer::new()
+        .includes(&includes)
+        .node_handler("heading-1", |_node, inner| format!("<h2>{}</h2>", inner))
+        .node_handler("table", |_node, _inner| String::new())
+        .mark_handler(MarkType::Bold, |text| format!("<strong>{}</strong>", text))
+        .embedded_entry_handler(|node, entry| match node {
+            Node::EmbeddedEntryBlock(_) => format!("<div>{}</div>", entry["name"]),
+            _ => format!("<span>{}</span>", entry["sys"]["id"]),
+        })
+        .render(&document);
+    assert!(actual.starts_with("<h2><strong>Title</strong></h2>"));
+    assert!(actual.contains("<span>\"inline-id\"</span></p>"));
+    assert!(actual.contains("<div>\"Saju\"</div>"));
+    assert!(!actual.contains("<table>"));
+}
+
+#[test]
+fn markdown_renderer_renders_commonmark() {
+    let document = serde_json::from_value::<Document>(rich_text()).unwrap();
+    let actual = MarkdownRenderer::new()
+        .embedded_entry_handler(|_node, entry| {
+            format!("{{{{{}}}}}", entry["sys"]["id"].as_str().unwrap())
+        })
+        .render(&document);
+    assert_eq!(
+        actual,
+        "# **Title**\n\
+        \n\
+        See [_example_](https://example.com){{inline-id}}\n\
+        \n\
+        - `Item`\n\
+        \n\
+        {{entry-id}}\n\
+        \n\
+        > Quote\n\
+        \n\
+        | A \\& \\<B\\> | 1 |\n\
+        | --- | --- |"
+    );
+
+    let actual = MarkdownRenderer::new().tables(false).render(&document);
+    assert!(actual.ends_with("> Quote"));
+}
+
+#[test]
+fn plain_text_renderer_renders_text() {
+    let document = serde_json::from_value::<Document>(rich_text()).unwrap();
+    let actual = PlainTextRenderer::new().render(&document);
+    assert_eq!(
+        actual,
+        "Title\n\nSee example\n\n- Item\n\nQuote\n\nA & <B>\t1"
+    );
+
+    let actual = PlainTextRenderer::new().tables(false).render(&document);
+    assert_eq!(actual, "Title\n\nSee example\n\n- Item\n\nQuote");
+}
+
+#[test]
+fn markdown_renderer_escapes_text() {
+    let document = serde_json::from_value::<Document>(json!({
+        "nodeType": "document",
+        "data": {},
+        "content": [
+            paragraph(vec![text("1. not a list - x", &[])]),
+            paragraph(vec![text("- a | b ~ c! &amp;", &[])]),
+            paragraph(vec![text("a`b", &["code"]), text(" and ", &[]), text("c", &["bold", "code"])]),
+            {
+                "nodeType": "table",
+                "data": {},
+                "content": [{
+                    "nodeType": "table-row",
+                    "data": {},
+                    "content": [{
+                        "nodeType": "table-header-cell",
+                        "data": {},
+                        "content": [paragraph(vec![text("x | y", &[])])]
+                    }]
+                }]
+            }
+        ]
+    }))
+    .unwrap();
+    let actual = MarkdownRenderer::new().render(&document);
+    assert_eq!(
+        actual,
+        "1\\. not a list - x\n\
+        \n\
+        \\- a \\| b \\~ c\\! \\&amp;\n\
+        \n\
+        ``a`b`` and **`c`**\n\
+        \n\
+        | x \\| y |\n\
+        | --- |"
+    );
+    assert_eq!(
+        serde_json::to_value(Document::from_markdown(&actual)).unwrap(),
+        serde_json::to_value(&document).unwrap()
+    );
+}
+
+#[test]
+fn from_markdown_keeps_only_paragraphs_in_quotes() {
+    let document = Document::from_markdown("> # Heading\n> - a\n>\n> > b\n> ---");
+    assert_eq!(
+        serde_json::to_value(&document).unwrap()["content"],
+        json!([{
+            "nodeType": "blockquote",
+            "data": {},
+            "content": [
+                paragraph(vec![text("Heading", &[])]),
+                paragraph(vec![text("a", &[])]),
+                paragraph(vec![text("b", &[])])
+            ]
+        }])
+    );
+}
+
+#[test]
+fn from_markdown_leaves_out_empty_quotes_and_lists() {
+    let document = Document::from_markdown("- \n- \n\n> \n\n1.\n\ntext");
+    assert_eq!(
+        serde_json::to_value(&document).unwrap()["content"],
+        json!([paragraph(vec![text("text", &[])])])
+    );
+}
+
+#[test]
+fn markdown_renderer_separates_paragraphs_of_list_items() {
+    let document = Document::from_markdown("- a\n\n  b\n  - c\n- d");
+    let actual = MarkdownRenderer::new().render(&document);
+    assert_eq!(actual, "- a\n\n  b\n  - c\n- d");
+    assert_eq!(Document::from_markdown(&actual), document);
+}
+
+#[test]
+fn plain_text_renderer_keeps_empty_table_cells() {
+    let document = Document::from_markdown("| a | b | c |\n| --- | --- | --- |\n| 1 |   | 3 |");
+    let actual = PlainTextRenderer::new().render(&document);
+    assert_eq!(actual, "a\tb\tc\n1\t\t3");
+}
+
+#[test]
+fn from_markdown_parses_commonmark() {
+    let markdown = "# Title\n\
+        \n\
+        Some **bold**, _italic_ and `code` with [a link](https://example.com).\n\
+        \n\
+        1. One\n\
+        2. Two\n   - Nested\n\
+        \n\
+        > Quote\n\
+        \n\
+        ---\n\
+        \n\
+        | A | B |\n\
+        | --- | --- |\n\
+        | 1 | 2 |";
+    let document = Document::from_markdown(markdown);
+
+    assert_eq!(
+        serde_json::to_value(&document.content[1]).unwrap(),
+        json!({
+            "nodeType": "paragraph",
+            "data": {},
+            "content": [
+                text("Some ", &[]),
+                text("bold", &["bold"]),
+                text(", ", &[]),
+                text("italic", &["italic"]),
+                text(" and ", &[]),
+                text("code", &["code"]),
+                text(" with ", &[]),
+                {
+                    "nodeType": "hyperlink",
+                    "data": { "uri": "https://example.com" },
+                    "content": [text("a link", &[])]
+                },
+                text(".", &[])
+            ]
+        })
+    );
+    match &document.content[2].content()[1].content()[1] {
+        Node::UnorderedList(list) => {
+            assert!(matches!(list.content[0].content()[0], Node::Paragraph(_)))
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+    assert!(matches!(
+        document.content[5].content()[0].content()[0],
+        Node::TableHeaderCell(_)
+    ));
+    assert_eq!(MarkdownRenderer::new().render(&document), markdown);
+}
+
+#[test]
+fn from_markdown_builds_valid_rich_text() {
+    let document = Document::from_markdown("| a | b |\n| --- | --- |\n| 1 |   |");
+    assert_eq!(
+        serde_json::to_value(&document.content[0].content()[1].content()[1]).unwrap(),
+        json!({
+            "nodeType": "table-cell",
+            "data": {},
+            "content": [{ "nodeType": "paragraph", "data": {}, "content": [text("", &[])] }]
+        })
+    );
+
+    let document = Document::from_markdown("[![img](x.png)](http://a)");
+    assert_eq!(
+        serde_json::to_value(&document.content[0]).unwrap(),
+        json!({
+            "nodeType": "paragraph",
+            "data": {},
+            "content": [{
+                "nodeType": "hyperlink",
+                "data": { "uri": "http://a" },
+                "content": [text("img", &[])]
+            }]
+        })
+    );
+}
+
+fn rich_text() -> Value {
+    json!({
+        "nodeType": "document",
+        "data": {},
+        "content": [
+            {
+                "nodeType": "heading-1",
+                "data": {},
+                "content": [text("Title", &["bold", "highlight"])]
+            },
+            {
+                "nodeType": "paragraph",
+                "data": {},
+                "content": [
+                    text("See ", &[]),
+                    {
+                        "nodeType": "hyperlink",
+                        "data": { "uri": "https://example.com" },
+                        "content": [text("example", &["italic"])]
+                    },
+                    {
+                        "nodeType": "embedded-entry-inline",
+                        "data": { "target": link("Entry", "inline-id") },
+                        "content": []
+                    }
+                ]
+            },
+            {
+                "nodeType": "unordered-list",
+                "data": {},
+                "content": [{
+                    "nodeType": "list-item",
+                    "data": {},
+                    "content": [{
+                        "nodeType": "paragraph",
+                        "data": {},
+                        "content": [text("Item", &["code"])]
+                    }]
+                }]
+            },
+            {
+                "nodeType": "embedded-entry-block",
+                "data": { "target": link("Entry", "entry-id") },
+                "content": []
+            },
+            {
+                "nodeType": "blockquote",
+                "data": {},
+                "content": [{
+                    "nodeType": "paragraph",
+                    "data": {},
+                    "content": [{
+                        "nodeType": "asset-hyperlink",
+                        "data": { "target": link("Asset", "asset-id") },
+                        "content": [text("Quote", &[])]
+                    }]
+                }]
+            },
+            {
+                "nodeType": "table",
+                "data": {},
+                "content": [{
+                    "nodeType": "table-row",
+                    "data": {},
+                    "content": [
+                        {
+                            "nodeType": "table-header-cell",
+                            "data": {},
+                            "content": [{ "nodeType": "paragraph", "data": {}, "content": [text("A & <B>", &[])] }]
+                        },
+                        {
+                            "nodeType": "table-cell",
+                            "data": {},
+                            "content": [{ "nodeType": "paragraph", "data": {}, "content": [text("1", &[])] }]
+                        }
+                    ]
+                }]
+            }
+        ]
+    })
+}
+
+fn paragraph(content: Vec<Value>) -> Value {
+    json!({ "nodeType": "paragraph", "data": {}, "content": content })
+}
+
+fn text(value: &str, marks: &[&str]) -> Value {
+    let marks = marks
+        .iter()
+        .map(|mark| json!({ "type": mark }))
+        .collect::<Vec<_>>();
+    json!({ "nodeType": "text", "value": value, "marks": marks, "data": {} })
+}
+
+fn link(link_type: &str, id: &str) -> Value {
+    json!({ "sys": { "type": "Link", "linkType": link_type, "id": id } })
+}
@@ -0,0 +1,82 @@
+use contentful::models::{LinkReference, SystemProperties};
+use serde_json::json;
+
+#[test]
+fn system_properties_deserializes_full_sys() {
+    let sys = serde_json::from_value::<SystemProperties>(json!({
+        "id": "entry-id",
+        "type": "Entry",
+        "space": { "sys": { "type": "Link", "linkType": "Space", "id": "space-id" } },
+        "environment": { "sys": { "type": "Link", "linkType": "Environment", "id": "master" } },
+        "contentType": { "sys": { "type": "Link", "linkType": "ContentType", "id": "person" } },
+        "createdBy": { "sys": { "type": "Link", "linkType": "User", "id": "user-id" } },
+        "updatedBy": { "sys": { "type": "Link", "linkType": "User", "id": "user-id" } },
+        "publishedBy": { "sys": { "type": "Link", "linkType": "User", "id": "user-id" } },
+        "version": 5,
+        "publishedVersion": 4,
+        "publishedCounter": 2,
+        "createdAt": "2021-01-01T00:00:00.000Z",
+        "updatedAt": "2021-01-03T00:00:00.000Z",
+        "publishedAt": "2021-01-03T00:00:00.000Z",
+        "firstPublishedAt": "2021-01-02T00:00:00.000Z"
+    }))
+    .unwrap();
+    assert_eq!(sys.sys_type.as_deref(), Some("Entry"));
+    assert_eq!(
+        sys.content_type,
+        Some(LinkReference::new("person", "ContentType"))
+    );
+    assert_eq!(sys.environment.unwrap().id, "master");
+    assert_eq!(sys.published_by.unwrap().link_type, "User");
+    assert_eq!(sys.published_counter, Some(2));
+    assert!(sys.first_published_at.unwrap() < sys.published_at.unwrap());
+}
+
+#[test]
+fn system_properties_status_helpers_work() {
+    let sys = |version: i32, published_version: Option<i32>, archived_version: Option<i32>| {
+        let mut sys = SystemProperties::with_version("entry-id".into(), version);
+        sys.published_version = published_version;
+        sys.archived_version = archived_version;
+        sys
+    };
+
+    let draft = sys(1, None, None);
+    assert!(draft.is_draft());
+    assert!(!draft.is_published() && !draft.is_changed() && !draft.is_archived());
+
+    let published = sys(2, Some(1), None);
+    assert!(published.is_published());
+    assert!(!published.is_draft() && !published.is_changed() && !published.is_archived());
+
+    let changed = sys(4, Some(1), None);
+    assert!(changed.is_changed());
+    assert!(!changed.is_draft() && !changed.is_published() && !changed.is_archived());
+
+    let archived = sys(3, None, Some(2));
+    assert!(archived.is_archived());
+    assert!(!archived.is_draft() && !archived.is_published() && !archived.is_changed());
+}
+
+#[test]
+fn system_properties_status_helpers_work_for_delivery_api() {
+    let sys = serde_json::from_value::<SystemProperties>(json!({
+        "id": "entry-id",
+        "type": "Entry",
+        "revision": 3,
+        "createdAt": "2021-01-01T00:00:00.000Z",
+        "updatedAt": "2021-01-03T00:00:00.000Z"
+    }))
+    .unwrap();
+    assert!(sys.is_published());
+    assert!(!sys.is_draft() && !sys.is_changed() && !sys.is_archived());
+}
+
+#[test]
+fn system_properties_serializes_only_set_fields() {
+    let sys = SystemProperties::with_version("entry-id".into(), 3);
+    assert_eq!(
+        serde_json::to_value(&sys).unwrap(),
+        json!({ "id": "entry-id", "version": 3 })
+    );
+}